@@ -0,0 +1,102 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::sgr::sgr_commands;
+use crate::{Attribute, Color, ColoredString, ANSI_ESCAPE_END};
+
+/// Attributes enabled by their code, in the order of their code from 1.
+const ATTRIBUTES: [Attribute; 9] = [
+    Attribute::Bold,
+    Attribute::Faint,
+    Attribute::Italic,
+    Attribute::Underline,
+    Attribute::SlowBlink,
+    Attribute::FastBlink,
+    Attribute::Reverse,
+    Attribute::Conceal,
+    Attribute::Strike,
+];
+
+/// Get the snake case name of an attribute, such as `slow_blink`.
+fn attribute_name(attribute: Attribute) -> String {
+    let mut name = String::new();
+
+    for ch in format!("{:?}", attribute).chars() {
+        if ch.is_uppercase() && !name.is_empty() {
+            name.push('_');
+        }
+
+        name.extend(ch.to_lowercase());
+    }
+
+    name
+}
+
+/// Get the name of the color of an extended color command (`38`, `48` and `58`) or of a
+/// basic color code.
+fn color_name(command: &[u8]) -> String {
+    match command {
+        [_, 5, index] => index.to_string(),
+        [_, 2, r, g, b] => format!("rgb({},{},{})", r, g, b),
+        [39 | 49 | 59] => "default".to_string(),
+        [code] => Color::from_code(*code).map_or_else(
+            || code.to_string(),
+            |color| color.to_string().to_lowercase().replace(' ', "_"),
+        ),
+        _ => "?".to_string(),
+    }
+}
+
+/// Get the readable annotation of a SGR command.
+fn annotation(command: &[u8]) -> String {
+    let code = command[0];
+
+    match code {
+        0 => "reset".to_string(),
+        1..=9 => attribute_name(ATTRIBUTES[code as usize - 1]),
+        22 => "normal_intensity".to_string(),
+        25 => "no_blink".to_string(),
+        23..=29 if code != 26 => format!("no_{}", attribute_name(ATTRIBUTES[code as usize - 21])),
+        30..=39 | 90..=97 => format!("fg:{}", color_name(command)),
+        40..=49 | 100..=107 => format!("bg:{}", color_name(command)),
+        58 | 59 => format!("underline_color:{}", color_name(command)),
+        _ => format!(
+            "sgr:{}",
+            command
+                .iter()
+                .map(u8::to_string)
+                .collect::<Vec<_>>()
+                .join(";")
+        ),
+    }
+}
+
+impl ColoredString {
+    /// Get the colored string like [`colored`](Self::colored), with readable annotations such
+    /// as `<fg:red>` or `<reset>` in place of the escape sequences. This is meant for tests and
+    /// documentation, where escape sequences are hard to read.
+    pub fn colored_annotated(&self) -> String {
+        let mut ret = String::with_capacity(self.raw.len());
+        let mut parts = self.render_parts();
+        let mut codes = Vec::new();
+
+        while let Some(part) = parts.next() {
+            if parts.is_text(part) {
+                ret.push_str(part);
+            } else if part == ANSI_ESCAPE_END {
+                for command in sgr_commands(&codes) {
+                    ret.push('<');
+                    ret.push_str(&annotation(command));
+                    ret.push('>');
+                }
+
+                codes.clear();
+            } else if let Ok(code) = part.parse() {
+                codes.push(code);
+            }
+        }
+
+        ret
+    }
+}
@@ -0,0 +1,216 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::ColoredString;
+
+/// Kind of escape sequence found in a string.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Escape {
+    /// Control sequence (`ESC [ ... final`), with its final byte.
+    Csi(u8),
+    /// Any other escape sequence: operating system command, character set selection...
+    Other,
+}
+
+/// Get the length and kind of the escape sequence at the beginning of `bytes`, which must start
+/// with `ESC`. An unterminated sequence extends to the end of `bytes`.
+fn escape_at(bytes: &[u8]) -> (usize, Escape) {
+    match bytes.get(1) {
+        Some(b'[') => {
+            // Parameter and intermediate bytes, then the final byte.
+            match bytes[2..]
+                .iter()
+                .position(|byte| !(0x20..=0x3f).contains(byte))
+            {
+                Some(position) if (0x40..=0x7e).contains(&bytes[2 + position]) => {
+                    (position + 3, Escape::Csi(bytes[2 + position]))
+                }
+                Some(position) => (position + 2, Escape::Other),
+                None => (bytes.len(), Escape::Other),
+            }
+        }
+        Some(b']') => {
+            // Terminated by BEL or by ST (`ESC \`).
+            let mut end = 2;
+
+            while end < bytes.len() {
+                match bytes[end] {
+                    0x07 => return (end + 1, Escape::Other),
+                    0x1b if bytes.get(end + 1) == Some(&b'\\') => return (end + 2, Escape::Other),
+                    _ => end += 1,
+                }
+            }
+
+            (end, Escape::Other)
+        }
+        Some(_) => {
+            // Intermediate bytes, then the final byte.
+            match bytes[1..]
+                .iter()
+                .position(|byte| !(0x20..=0x2f).contains(byte))
+            {
+                Some(position) if (0x30..=0x7e).contains(&bytes[1 + position]) => {
+                    (position + 2, Escape::Other)
+                }
+                Some(position) => (position + 1, Escape::Other),
+                None => (bytes.len(), Escape::Other),
+            }
+        }
+        None => (1, Escape::Other),
+    }
+}
+
+/// Event found by [`AnsiParser`] in a string holding ANSI escape sequences.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AnsiEvent<'a> {
+    /// Text, free of escape sequences.
+    Text(&'a str),
+    /// Styling (SGR) escape sequence, with its parameters. An empty parameter is a 0, as
+    /// interpreted by terminals, and a parameter over 255 is clamped.
+    Sgr(Vec<u8>),
+    /// Any other escape sequence, such as a cursor movement, as is.
+    Escape(&'a str),
+}
+
+/// Iterator over the text and escape sequences of a string, to build renderers or transformers
+/// of ANSI output.
+pub struct AnsiParser<'a> {
+    s: &'a str,
+    index: usize,
+}
+
+impl<'a> AnsiParser<'a> {
+    /// Create a parser over `s`.
+    pub fn new(s: &'a str) -> Self {
+        AnsiParser { s, index: 0 }
+    }
+}
+
+/// Get the value of a SGR parameter. An empty parameter is a 0 and a parameter over 255 is
+/// clamped.
+fn param_value(param: &str) -> u8 {
+    param
+        .bytes()
+        .filter(u8::is_ascii_digit)
+        .fold(0u8, |value, digit| {
+            value.saturating_mul(10).saturating_add(digit - b'0')
+        })
+}
+
+/// Push the codes of a command given with `:` separated sub-parameters, in their `;` form.
+fn push_sub_params(codes: &mut Vec<u8>, command: &str) {
+    let values: Vec<u8> = command.split(':').map(param_value).collect();
+
+    match values[..] {
+        // The color space identifier of `38:2:[cs]:r:g:b` has no `;` equivalent.
+        [code @ (38 | 48 | 58), 2, .., r, g, b] => codes.extend([code, 2, r, g, b]),
+        [code @ (38 | 48 | 58), 5, n, ..] => codes.extend([code, 5, n]),
+        // Underline styles (curly, dotted...), all rendered as a plain underline.
+        [4, 0, ..] => codes.push(24),
+        [4, ..] => codes.push(4),
+        [code, ..] => codes.push(code),
+        [] => (),
+    }
+}
+
+/// Get the parameters of a SGR sequence, from the text between `ESC [` and `m`.
+fn sgr_params(params: &str) -> Vec<u8> {
+    let mut codes = Vec::new();
+
+    for command in params.split(';') {
+        if command.contains(':') {
+            push_sub_params(&mut codes, command);
+        } else {
+            codes.push(param_value(command));
+        }
+    }
+
+    codes
+}
+
+impl<'a> Iterator for AnsiParser<'a> {
+    type Item = AnsiEvent<'a>;
+
+    fn next(&mut self) -> Option<AnsiEvent<'a>> {
+        let rest = &self.s[self.index..];
+
+        if rest.is_empty() {
+            return None;
+        }
+
+        match rest.find('\x1b') {
+            Some(0) => {
+                let (len, escape) = escape_at(rest.as_bytes());
+
+                self.index += len;
+
+                match escape {
+                    Escape::Csi(b'm') => Some(AnsiEvent::Sgr(sgr_params(&rest[2..len - 1]))),
+                    _ => Some(AnsiEvent::Escape(&rest[..len])),
+                }
+            }
+            Some(position) => {
+                self.index += position;
+                Some(AnsiEvent::Text(&rest[..position]))
+            }
+            None => {
+                self.index = self.s.len();
+                Some(AnsiEvent::Text(rest))
+            }
+        }
+    }
+}
+
+/// Remove all the escape sequences from `s`: styling, cursor movements, window title settings
+/// and so on, leaving only the text.
+pub fn strip_ansi(s: &str) -> String {
+    AnsiParser::new(s)
+        .filter_map(|event| match event {
+            AnsiEvent::Text(text) => Some(text),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Remove only the styling (SGR, `ESC [ ... m`) escape sequences from `s`. Unlike
+/// [`strip_ansi`], the other control sequences, such as cursor movements, are left intact.
+pub fn strip_sgr(s: &str) -> String {
+    AnsiParser::new(s)
+        .filter_map(|event| match event {
+            AnsiEvent::Text(text) | AnsiEvent::Escape(text) => Some(text),
+            AnsiEvent::Sgr(_) => None,
+        })
+        .collect()
+}
+
+impl ColoredString {
+    /// Parse the styled output of a program into a colored string, the reverse of
+    /// [`colored`](Self::colored). The styling codes are kept as is, so the 16 colors, the 256
+    /// colors, the RGB colors and the attributes survive a parsing followed by a rendering.
+    /// Only the parameters are approximated: the ones over 255 are clamped and the commands
+    /// with `:` separated sub-parameters take their `;` form, `38:2:[cs]:r:g:b` becoming
+    /// `38;2;r;g;b` without its color space and the underline styles `4:n` becoming `4` or
+    /// `24`. The escape sequences other than the styling ones, such as cursor movements, are
+    /// dropped.
+    pub fn parse_ansi(s: &str) -> ColoredString {
+        let mut ret = ColoredString::with_capacity(s.len());
+
+        ret.push_ansi_str(s);
+        ret
+    }
+
+    /// Push a string which may already hold escape sequences, such as the output of another
+    /// program. Its styling codes are turned into codes of the colored string instead of being
+    /// embedded in the raw content, the other escape sequences being dropped, see
+    /// [`parse_ansi`](Self::parse_ansi). The codes apply on top of the current styling and
+    /// stay active after the pushed string.
+    pub fn push_ansi_str(&mut self, s: &str) {
+        for event in AnsiParser::new(s) {
+            match event {
+                AnsiEvent::Text(text) => self.push_str(text),
+                AnsiEvent::Sgr(codes) => self.push_raw_sgr(&codes),
+                AnsiEvent::Escape(_) => (),
+            }
+        }
+    }
+}
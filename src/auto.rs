@@ -0,0 +1,84 @@
+use std::ffi::OsStr;
+#[cfg(not(target_arch = "wasm32"))]
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use crate::ColoredString;
+
+const FORCE_NONE: u8 = 0;
+const FORCE_OFF: u8 = 1;
+const FORCE_ON: u8 = 2;
+
+/// Override of the color detection, set by [`force_color`].
+static FORCE_COLOR: AtomicU8 = AtomicU8::new(FORCE_NONE);
+
+/// Force the colors on or off for the whole program, whatever the environment and the terminal,
+/// until [`clear_force_color`] is called. This is meant for tests and CI pipelines which capture
+/// the output.
+pub fn force_color(enabled: bool) {
+    FORCE_COLOR.store(
+        if enabled { FORCE_ON } else { FORCE_OFF },
+        Ordering::Relaxed,
+    );
+}
+
+/// Remove the override set by [`force_color`], going back to the detection.
+pub fn clear_force_color() {
+    FORCE_COLOR.store(FORCE_NONE, Ordering::Relaxed);
+}
+
+/// Check whether colored output should be written to the standard output: it must be a
+/// terminal, the `NO_COLOR` environment variable must not be set (to a non-empty value) and the
+/// `TERM` environment variable must be set to something else than `dumb`, such terminals not
+/// interpreting escape sequences. An override set by [`force_color`] takes precedence.
+///
+/// On WASM, where the environment and the terminal cannot be inspected reliably, the output is
+/// always colored unless disabled by [`force_color`].
+pub fn should_colorize() -> bool {
+    match FORCE_COLOR.load(Ordering::Relaxed) {
+        FORCE_OFF => false,
+        FORCE_ON => true,
+        _ => detect(),
+    }
+}
+
+/// Detect whether the standard output accepts colors, see [`should_colorize`].
+#[cfg(target_arch = "wasm32")]
+fn detect() -> bool {
+    true
+}
+
+/// Detect whether the standard output accepts colors, see [`should_colorize`].
+#[cfg(not(target_arch = "wasm32"))]
+fn detect() -> bool {
+    supports_color(
+        std::env::var_os("TERM").as_deref(),
+        std::env::var_os("NO_COLOR").as_deref(),
+        std::io::stdout().is_terminal(),
+    )
+}
+
+/// Decide whether an output accepts colors from the values of the `TERM` and `NO_COLOR`
+/// environment variables and whether the output is a terminal, following the rules of
+/// [`should_colorize`] without inspecting the environment.
+pub fn supports_color(term: Option<&OsStr>, no_color: Option<&OsStr>, is_terminal: bool) -> bool {
+    if no_color.is_some_and(|value| !value.is_empty()) {
+        return false;
+    }
+
+    match term {
+        Some(term) => term != "dumb" && is_terminal,
+        None => false,
+    }
+}
+
+impl ColoredString {
+    /// Get the colored string if [`should_colorize`] allows it, the raw content otherwise.
+    pub fn colored_auto(&self) -> String {
+        if should_colorize() {
+            self.colored()
+        } else {
+            self.raw()
+        }
+    }
+}
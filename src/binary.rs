@@ -0,0 +1,155 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{CodeMarker, ColoredString};
+
+/// Version of the binary format written by [`ColoredString::to_bytes`].
+const VERSION: u8 = 1;
+
+/// Error found while decoding a colored string with [`ColoredString::from_bytes`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The data was written with an unknown version of the format.
+    UnsupportedVersion(u8),
+    /// The data ends before the end of the colored string.
+    Truncated,
+    /// The raw content is not valid UTF-8.
+    InvalidUtf8,
+    /// A marker is empty, out of order, or not on a character boundary of the raw content.
+    InvalidMarker,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DecodeError::UnsupportedVersion(version) => {
+                write!(f, "unsupported format version {}", version)
+            }
+            DecodeError::Truncated => write!(f, "truncated data"),
+            DecodeError::InvalidUtf8 => write!(f, "raw content is not valid UTF-8"),
+            DecodeError::InvalidMarker => write!(f, "invalid code marker"),
+        }
+    }
+}
+
+impl core::error::Error for DecodeError {}
+
+/// Append `value` to `out` as a LEB128 variable-length integer.
+fn write_len(out: &mut Vec<u8>, mut value: usize) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+
+        value >>= 7;
+
+        if value == 0 {
+            out.push(byte);
+            return;
+        }
+
+        out.push(byte | 0x80);
+    }
+}
+
+/// Reader over the encoded data.
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], DecodeError> {
+        if len > self.data.len() {
+            return Err(DecodeError::Truncated);
+        }
+
+        let (bytes, rest) = self.data.split_at(len);
+
+        self.data = rest;
+        Ok(bytes)
+    }
+
+    fn byte(&mut self) -> Result<u8, DecodeError> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    /// Read a LEB128 variable-length integer, see [`write_len`].
+    fn len(&mut self) -> Result<usize, DecodeError> {
+        let mut value: usize = 0;
+
+        for shift in (0..usize::BITS).step_by(7) {
+            let byte = self.byte()?;
+
+            value |= ((byte & 0x7f) as usize)
+                .checked_shl(shift)
+                .ok_or(DecodeError::Truncated)?;
+
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+
+        Err(DecodeError::Truncated)
+    }
+}
+
+impl ColoredString {
+    /// Encode the raw content and the codes into a compact binary form, to cache the styled
+    /// string and load it back with [`from_bytes`](Self::from_bytes).
+    ///
+    /// The format is a version byte, the length of the raw content and its bytes, the number
+    /// of markers, then for each marker its index, its number of codes and the codes. The
+    /// lengths, counts and indexes are LEB128 variable-length integers.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut ret = Vec::with_capacity(self.raw.len() + self.code_markers.len() * 4 + 4);
+
+        ret.push(VERSION);
+        write_len(&mut ret, self.raw.len());
+        ret.extend_from_slice(self.raw.as_bytes());
+        write_len(&mut ret, self.code_markers.len());
+
+        for marker in self.code_markers.iter() {
+            write_len(&mut ret, marker.index);
+            write_len(&mut ret, marker.codes.len());
+            ret.extend_from_slice(&marker.codes);
+        }
+
+        ret
+    }
+
+    /// Decode a colored string encoded by [`to_bytes`](Self::to_bytes).
+    pub fn from_bytes(bytes: &[u8]) -> Result<ColoredString, DecodeError> {
+        let mut reader = Reader { data: bytes };
+        let version = reader.byte()?;
+
+        if version != VERSION {
+            return Err(DecodeError::UnsupportedVersion(version));
+        }
+
+        let len = reader.len()?;
+        let raw = core::str::from_utf8(reader.bytes(len)?).map_err(|_| DecodeError::InvalidUtf8)?;
+        let mut ret = ColoredString::from(raw);
+        let count = reader.len()?;
+
+        for _ in 0..count {
+            let index = reader.len()?;
+            let len = reader.len()?;
+            let codes = reader.bytes(len)?;
+
+            if codes.is_empty()
+                || !raw.is_char_boundary(index)
+                || ret
+                    .code_markers
+                    .last()
+                    .is_some_and(|last| last.index > index)
+            {
+                return Err(DecodeError::InvalidMarker);
+            }
+
+            ret.code_markers.push(CodeMarker {
+                index,
+                codes: codes.to_vec(),
+            });
+        }
+
+        Ok(ret)
+    }
+}
@@ -0,0 +1,102 @@
+use alloc::string::String;
+
+use crate::{Color, ColoredString, Style};
+
+/// Extension trait to color strings in place, such as `"error".red().bold()`. Each method gives
+/// a colored string with the styling set at its beginning, on top of the one already there.
+pub trait Colorize: Sized {
+    /// Get the colored string with `style` set at its beginning.
+    fn styled(self, style: &Style) -> ColoredString;
+
+    /// Set the foreground color.
+    fn fg(self, color: Color) -> ColoredString {
+        self.styled(&Style::new().fg(color))
+    }
+
+    /// Set the background color.
+    fn bg(self, color: Color) -> ColoredString {
+        self.styled(&Style::new().bg(color))
+    }
+
+    fn black(self) -> ColoredString {
+        self.fg(Color::Black)
+    }
+
+    fn red(self) -> ColoredString {
+        self.fg(Color::Red)
+    }
+
+    fn green(self) -> ColoredString {
+        self.fg(Color::Green)
+    }
+
+    fn yellow(self) -> ColoredString {
+        self.fg(Color::Yellow)
+    }
+
+    fn blue(self) -> ColoredString {
+        self.fg(Color::Blue)
+    }
+
+    fn magenta(self) -> ColoredString {
+        self.fg(Color::Magenta)
+    }
+
+    fn cyan(self) -> ColoredString {
+        self.fg(Color::Cyan)
+    }
+
+    fn white(self) -> ColoredString {
+        self.fg(Color::White)
+    }
+
+    fn bold(self) -> ColoredString {
+        self.styled(&Style::new().bold())
+    }
+
+    fn faint(self) -> ColoredString {
+        self.styled(&Style::new().faint())
+    }
+
+    fn italic(self) -> ColoredString {
+        self.styled(&Style::new().italic())
+    }
+
+    fn underline(self) -> ColoredString {
+        self.styled(&Style::new().underline())
+    }
+
+    fn reverse(self) -> ColoredString {
+        self.styled(&Style::new().reverse())
+    }
+
+    fn strike(self) -> ColoredString {
+        self.styled(&Style::new().strike())
+    }
+}
+
+impl Colorize for &str {
+    fn styled(self, style: &Style) -> ColoredString {
+        let mut ret = ColoredString::with_capacity(self.len());
+
+        ret.set_style(style);
+        ret.push_str(self);
+        ret
+    }
+}
+
+impl Colorize for String {
+    fn styled(self, style: &Style) -> ColoredString {
+        ColoredString::from(self).styled(style)
+    }
+}
+
+impl Colorize for ColoredString {
+    fn styled(mut self, style: &Style) -> ColoredString {
+        for code in style.codes() {
+            self.insert_code_at(0, code);
+        }
+
+        self
+    }
+}
@@ -0,0 +1,132 @@
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::ColoredString;
+
+/// Kind of difference reported by [`ColoredString::diff`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiffKind {
+    /// Text only present in the other string.
+    Added,
+    /// Text only present in the original string.
+    Removed,
+    /// Text replaced, or styled differently, in the other string.
+    Changed,
+}
+
+/// A difference between two colored strings, as byte ranges in the raw content of each of them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DiffSpan {
+    pub kind: DiffKind,
+    /// Range in the original string.
+    pub old: Range<usize>,
+    /// Range in the other string.
+    pub new: Range<usize>,
+}
+
+/// Characters of a colored string, with their byte index and the codes styling them.
+fn styled_chars(cs: &ColoredString) -> Vec<(usize, char, Vec<u8>)> {
+    let mut ret = Vec::with_capacity(cs.raw.len());
+    let mut codes = Vec::new();
+    let mut markers = cs.code_markers.iter().peekable();
+
+    for (index, ch) in cs.raw.char_indices() {
+        while let Some(marker) = markers.next_if(|marker| marker.index <= index) {
+            marker.apply(&mut codes);
+        }
+
+        ret.push((index, ch, codes.clone()));
+    }
+
+    ret
+}
+
+impl ColoredString {
+    /// Compare with `other`, reporting where the text or its styling differ. The text is
+    /// compared as the longest common subsequence of characters, each contiguous run of
+    /// differences giving one span.
+    pub fn diff(&self, other: &ColoredString) -> Vec<DiffSpan> {
+        let old = styled_chars(self);
+        let new = styled_chars(other);
+
+        // lengths[i][j] is the length of the longest common subsequence of old[i..] and new[j..].
+        let mut lengths = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+
+        for i in (0..old.len()).rev() {
+            for j in (0..new.len()).rev() {
+                lengths[i][j] = if old[i].1 == new[j].1 {
+                    lengths[i + 1][j + 1] + 1
+                } else {
+                    lengths[i + 1][j].max(lengths[i][j + 1])
+                };
+            }
+        }
+
+        let old_index = |i: usize| old.get(i).map_or(self.raw.len(), |(index, _, _)| *index);
+        let new_index = |j: usize| new.get(j).map_or(other.raw.len(), |(index, _, _)| *index);
+
+        let mut spans = Vec::new();
+        // Start of the current run of differences, along with whether it removed or added
+        // anything.
+        let mut run: Option<(usize, usize, bool, bool)> = None;
+        let (mut i, mut j) = (0, 0);
+
+        while i < old.len() || j < new.len() {
+            let (next_i, next_j) = if i < old.len() && j < new.len() && old[i].1 == new[j].1 {
+                (i + 1, j + 1)
+            } else if j < new.len() && (i == old.len() || lengths[i][j + 1] >= lengths[i + 1][j]) {
+                (i, j + 1)
+            } else {
+                (i + 1, j)
+            };
+
+            let same = next_i > i && next_j > j && old[i].2 == new[j].2;
+
+            if same {
+                if let Some(span) = close_run(run.take(), old_index(i), new_index(j)) {
+                    spans.push(span);
+                }
+            } else {
+                let (start_i, start_j, removed, added) =
+                    run.unwrap_or((old_index(i), new_index(j), false, false));
+
+                run = Some((
+                    start_i,
+                    start_j,
+                    removed || next_j == j,
+                    added || next_i == i,
+                ));
+            }
+
+            (i, j) = (next_i, next_j);
+        }
+
+        if let Some(span) = close_run(run, self.raw.len(), other.raw.len()) {
+            spans.push(span);
+        }
+
+        spans
+    }
+}
+
+/// Turn a run of differences ending at `end_i` and `end_j` into a span.
+fn close_run(
+    run: Option<(usize, usize, bool, bool)>,
+    end_i: usize,
+    end_j: usize,
+) -> Option<DiffSpan> {
+    let (start_i, start_j, removed, added) = run?;
+
+    let kind = match (removed, added) {
+        (true, false) => DiffKind::Removed,
+        (false, true) => DiffKind::Added,
+        _ => DiffKind::Changed,
+    };
+
+    Some(DiffSpan {
+        kind,
+        old: start_i..end_i,
+        new: start_j..end_j,
+    })
+}
@@ -0,0 +1,98 @@
+use crate::{round, Color, ColoredString};
+
+/// Composite `fg` over `bg` with the given opacity, clamped to `0.0..=1.0`, as with the
+/// source-over operator. Terminals have no transparency, this gives the solid color
+/// approximating a translucent one over a known background.
+pub fn over(fg: (u8, u8, u8), bg: (u8, u8, u8), alpha: f32) -> (u8, u8, u8) {
+    let alpha = alpha.clamp(0.0, 1.0);
+    let mix = |fg: u8, bg: u8| round(bg as f32 + (fg as f32 - bg as f32) * alpha) as u8;
+
+    (mix(fg.0, bg.0), mix(fg.1, bg.1), mix(fg.2, bg.2))
+}
+
+/// Get the position, from 0 to 1, of the `i`th of `count` characters.
+fn progress(i: usize, count: usize) -> f32 {
+    if count > 1 {
+        i as f32 / (count - 1) as f32
+    } else {
+        0.0
+    }
+}
+
+/// Color `text` with `color` fading across its characters, as if its opacity went from
+/// `from_alpha` to `to_alpha` over a black terminal background. See [`fade_on`] for other
+/// backgrounds.
+pub fn fade(text: &str, color: (u8, u8, u8), from_alpha: f32, to_alpha: f32) -> ColoredString {
+    fade_on(text, color, (0, 0, 0), from_alpha, to_alpha)
+}
+
+/// Color `text` with `color` fading across its characters, as if its opacity went from
+/// `from_alpha` to `to_alpha` over the `background` color of the terminal. Each character gets
+/// its own RGB foreground color.
+pub fn fade_on(
+    text: &str,
+    color: (u8, u8, u8),
+    background: (u8, u8, u8),
+    from_alpha: f32,
+    to_alpha: f32,
+) -> ColoredString {
+    let count = text.chars().count();
+    let mut ret = ColoredString::with_capacities(text.len(), count);
+
+    for (i, ch) in text.chars().enumerate() {
+        let alpha = from_alpha + (to_alpha - from_alpha) * progress(i, count);
+        let (r, g, b) = over(color, background, alpha);
+
+        ret.set_fg_rgb(r, g, b);
+        ret.push(ch);
+    }
+
+    ret
+}
+
+/// Color `text` with a gradient going through the `colors` in order, evenly distributed across
+/// its characters. Between two colors, each character gets an RGB foreground interpolated from
+/// their [`Color::rgb`] values. Without colors, the text is left unstyled.
+pub fn palette_gradient(text: &str, colors: &[Color]) -> ColoredString {
+    let count = text.chars().count();
+    let mut ret = ColoredString::with_capacities(text.len(), count);
+
+    for (i, ch) in text.chars().enumerate() {
+        if let Some(last) = colors.len().checked_sub(1) {
+            let position = progress(i, count) * last as f32;
+            let stop = (position as usize).min(last.saturating_sub(1));
+            let next = (stop + 1).min(last);
+            let (r, g, b) = over(
+                colors[next].rgb(),
+                colors[stop].rgb(),
+                position - stop as f32,
+            );
+
+            ret.set_fg_rgb(r, g, b);
+        }
+
+        ret.push(ch);
+    }
+
+    ret
+}
+
+/// Draw a progress bar of `width` cells, the `fraction` of them filled with `█` in the `filled`
+/// color and the others with `░` in the `empty` color. The fraction is clamped to `0.0..=1.0`
+/// and rounded to the nearest cell.
+pub fn bar(fraction: f32, width: usize, filled: Color, empty: Color) -> ColoredString {
+    let cells = round(fraction.clamp(0.0, 1.0) * width as f32);
+    let mut ret = ColoredString::with_capacity(width * 3);
+
+    if cells > 0 {
+        ret.set_fg(&filled);
+        ret.push_str(&"█".repeat(cells));
+    }
+
+    if cells < width {
+        ret.set_fg(&empty);
+        ret.push_str(&"░".repeat(width - cells));
+    }
+
+    ret
+}
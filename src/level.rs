@@ -0,0 +1,156 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use crate::sgr::sgr_commands;
+use crate::{CodeMarker, Color, ColoredString};
+
+/// Color support of a terminal, from none to 24-bit colors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ColorLevel {
+    /// No escape sequence at all.
+    NoColor,
+    /// The 16 basic colors.
+    Ansi16,
+    /// The 256 colors palette.
+    Ansi256,
+    /// 24-bit RGB colors.
+    TrueColor,
+}
+
+/// Levels of the components of the 6×6×6 color cube of the 256 colors palette.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// Flag of the entries of [`RGB_PALETTE`] holding an RGB value, the others being unset.
+const RGB_PALETTE_SET: u32 = 1 << 24;
+
+/// Override of the RGB values of the 16 basic colors, set by [`set_rgb_palette`].
+static RGB_PALETTE: [AtomicU32; 16] = [const { AtomicU32::new(0) }; 16];
+
+/// Set the RGB values of the 16 basic colors, in declaration order, for the whole program, such
+/// as to match the theme of a terminal. They are used by the renderings needing RGB values,
+/// [`ColoredString::render_for`] for its downgrades and `to_svg`, instead of the default xterm
+/// palette. [`Color::rgb`] is not affected.
+///
+/// This is unrelated to [`Palette`](crate::Palette), which maps semantic names to colors.
+pub fn set_rgb_palette(palette: [(u8, u8, u8); 16]) {
+    for (entry, (r, g, b)) in RGB_PALETTE.iter().zip(palette) {
+        entry.store(
+            RGB_PALETTE_SET | (r as u32) << 16 | (g as u32) << 8 | b as u32,
+            Ordering::Relaxed,
+        );
+    }
+}
+
+/// Remove the override set by [`set_rgb_palette`], going back to the default xterm palette.
+pub fn clear_rgb_palette() {
+    for entry in RGB_PALETTE.iter() {
+        entry.store(0, Ordering::Relaxed);
+    }
+}
+
+/// Get the RGB value of a color of the 256 colors palette, as in the default xterm palette
+/// unless overridden by [`set_rgb_palette`].
+pub(crate) fn palette_rgb(index: u8) -> (u8, u8, u8) {
+    match index {
+        0..=15 => {
+            let entry = RGB_PALETTE[index as usize].load(Ordering::Relaxed);
+
+            if entry & RGB_PALETTE_SET == 0 {
+                Color::ALL[index as usize].rgb()
+            } else {
+                ((entry >> 16) as u8, (entry >> 8) as u8, entry as u8)
+            }
+        }
+        16..=231 => {
+            let index = index - 16;
+
+            (
+                CUBE_LEVELS[(index / 36) as usize],
+                CUBE_LEVELS[(index / 6 % 6) as usize],
+                CUBE_LEVELS[(index % 6) as usize],
+            )
+        }
+        _ => {
+            let level = 8 + 10 * (index - 232);
+
+            (level, level, level)
+        }
+    }
+}
+
+/// Get the squared distance between two RGB colors.
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> i32 {
+    (a.0 as i32 - b.0 as i32).pow(2)
+        + (a.1 as i32 - b.1 as i32).pow(2)
+        + (a.2 as i32 - b.2 as i32).pow(2)
+}
+
+/// Get the basic color closest to `rgb`, see [`palette_rgb`].
+fn nearest_basic(rgb: (u8, u8, u8)) -> Color {
+    Color::ALL
+        .into_iter()
+        .min_by_key(|color| distance(palette_rgb(*color as u8), rgb))
+        .unwrap_or(Color::Black)
+}
+
+/// Get the index of the color of the cube or of the grayscale ramp of the 256 colors palette
+/// closest to `rgb`.
+fn nearest_256(rgb: (u8, u8, u8)) -> u8 {
+    (16..=255)
+        .min_by_key(|index| distance(palette_rgb(*index), rgb))
+        .unwrap_or(16)
+}
+
+/// Get the codes of a command adapted to the color support `level`, see
+/// [`ColoredString::render_for`].
+fn downgrade(command: &[u8], level: ColorLevel, out: &mut Vec<u8>) {
+    let rgb = match command {
+        [_, 2, r, g, b] => (*r, *g, *b),
+        [_, 5, index] => palette_rgb(*index),
+        _ => return out.extend_from_slice(command),
+    };
+
+    match (level, command[0]) {
+        (ColorLevel::Ansi256, _) => out.extend_from_slice(&[command[0], 5, nearest_256(rgb)]),
+        // The underline color has no basic code.
+        (_, 58) => (),
+        (_, base) => out.push(base - 8 + nearest_basic(rgb).int_value()),
+    }
+}
+
+impl ColoredString {
+    /// Get the colored string for a terminal with the given color support. The colors the
+    /// terminal does not support are approximated with the closest ones of the default xterm
+    /// palette, or of the one set by [`set_rgb_palette`]: the RGB colors become colors of the
+    /// 256 colors palette, and both become one of the 16 basic colors, the underline color
+    /// being dropped in that case. The other codes are kept as is. Without color support, only
+    /// the raw content is left.
+    pub fn render_for(&self, level: ColorLevel) -> String {
+        match level {
+            ColorLevel::NoColor => return self.raw.clone(),
+            ColorLevel::TrueColor => return self.colored(),
+            _ => (),
+        }
+
+        let mut ret = ColoredString::with_capacities(0, self.code_markers.len());
+
+        for marker in self.code_markers.iter() {
+            let mut codes = Vec::with_capacity(marker.codes.len());
+
+            for command in sgr_commands(&marker.codes) {
+                downgrade(command, level, &mut codes);
+            }
+
+            if !codes.is_empty() {
+                ret.code_markers.push(CodeMarker {
+                    index: marker.index,
+                    codes,
+                });
+            }
+        }
+
+        ret.raw.clone_from(&self.raw);
+        ret.colored()
+    }
+}
@@ -1,6 +1,63 @@
-use std::fmt;
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::borrow::Cow;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use core::fmt;
+
+use sgr::{fold_commands, sgr_commands, SgrState};
+
+mod annotate;
+mod ansi;
+#[cfg(feature = "std")]
+mod auto;
+mod binary;
+mod colorize;
+mod diff;
+mod effects;
+mod level;
+mod markup;
+mod palette;
+pub mod prelude;
+mod render;
+mod segments;
+mod sgr;
+mod style;
+#[cfg(feature = "svg")]
+mod svg;
+mod table;
+mod validate;
+#[cfg(feature = "std")]
+mod write;
+
+pub use ansi::{strip_ansi, strip_sgr, AnsiEvent, AnsiParser};
+#[cfg(feature = "std")]
+pub use auto::{clear_force_color, force_color, should_colorize, supports_color};
+pub use binary::DecodeError;
+pub use colorize::Colorize;
+pub use diff::{DiffKind, DiffSpan};
+pub use effects::{bar, fade, fade_on, over, palette_gradient};
+pub use level::{clear_rgb_palette, set_rgb_palette, ColorLevel};
+pub use markup::MarkupError;
+pub use palette::{level_color, style_level, theme_dark, theme_light, LogLevel, Palette};
+pub use render::LazyColored;
+pub use segments::Segments;
+pub use style::{transition_codes, ActiveColor, ActiveStyle, Style, StyleSnapshot};
+pub use table::Table;
+pub use validate::StyleWarning;
+#[cfg(feature = "std")]
+pub use write::colorize_stream;
+
+/// Offset of each color from the base code of its kind (30 for the foreground, 40 for the
+/// background), indexed by the color in declaration order: the bright colors are 60 above
+/// their normal variant.
+pub const COLOR_CODES: [u8; 16] = [0, 1, 2, 3, 4, 5, 6, 7, 60, 61, 62, 63, 64, 65, 66, 67];
 
 /// The basic 16 colors you can use for ANSI.
+#[derive(Clone, Copy, PartialEq, Eq)]
 pub enum Color {
     Black,
     Red,
@@ -21,26 +78,100 @@ pub enum Color {
 }
 
 impl Color {
+    /// All the colors, in declaration order.
+    pub const ALL: [Color; 16] = [
+        Color::Black,
+        Color::Red,
+        Color::Green,
+        Color::Yellow,
+        Color::Blue,
+        Color::Magenta,
+        Color::Cyan,
+        Color::White,
+        Color::BrightBlack,
+        Color::BrightRed,
+        Color::BrightGreen,
+        Color::BrightYellow,
+        Color::BrightBlue,
+        Color::BrightMagenta,
+        Color::BrightCyan,
+        Color::BrightWhite,
+    ];
+
+    /// Get the offset of the color from the base code of its kind, see [`COLOR_CODES`].
     pub fn int_value(&self) -> u8 {
+        COLOR_CODES[*self as usize]
+    }
+
+    /// Get the color following this one in declaration order, wrapping around from
+    /// `BrightWhite` to `Black`.
+    pub fn next(&self) -> Color {
+        Color::ALL[(*self as usize + 1) % Color::ALL.len()]
+    }
+
+    /// Get the color preceding this one in declaration order, wrapping around from `Black` to
+    /// `BrightWhite`.
+    pub fn prev(&self) -> Color {
+        Color::ALL[(*self as usize + Color::ALL.len() - 1) % Color::ALL.len()]
+    }
+
+    /// Get the RGB value of the color, as in the default xterm palette. Actual terminals may
+    /// use different values depending on their theme.
+    pub fn rgb(&self) -> (u8, u8, u8) {
         match self {
-            Color::Black => 0,
-            Color::Red => 1,
-            Color::Green => 2,
-            Color::Yellow => 3,
-            Color::Blue => 4,
-            Color::Magenta => 5,
-            Color::Cyan => 6,
-            Color::White => 7,
-            Color::BrightBlack => 60,
-            Color::BrightRed => 61,
-            Color::BrightGreen => 62,
-            Color::BrightYellow => 63,
-            Color::BrightBlue => 64,
-            Color::BrightMagenta => 65,
-            Color::BrightCyan => 66,
-            Color::BrightWhite => 67,
+            Color::Black => (0, 0, 0),
+            Color::Red => (205, 0, 0),
+            Color::Green => (0, 205, 0),
+            Color::Yellow => (205, 205, 0),
+            Color::Blue => (0, 0, 238),
+            Color::Magenta => (205, 0, 205),
+            Color::Cyan => (0, 205, 205),
+            Color::White => (229, 229, 229),
+            Color::BrightBlack => (127, 127, 127),
+            Color::BrightRed => (255, 0, 0),
+            Color::BrightGreen => (0, 255, 0),
+            Color::BrightYellow => (255, 255, 0),
+            Color::BrightBlue => (92, 92, 255),
+            Color::BrightMagenta => (255, 0, 255),
+            Color::BrightCyan => (0, 255, 255),
+            Color::BrightWhite => (255, 255, 255),
+        }
+    }
+
+    /// Get the color of a foreground (30–37, 90–97) or background (40–47, 100–107) code.
+    fn from_code(code: u8) -> Option<Color> {
+        match code {
+            30..=37 | 40..=47 => Some(Color::ALL[(code % 10) as usize]),
+            90..=97 | 100..=107 => Some(Color::ALL[(code % 10) as usize + 8]),
+            _ => None,
         }
     }
+
+    /// Get the color whose [`rgb`](Self::rgb) value is the closest to the given one.
+    pub fn nearest_ansi16(rgb: (u8, u8, u8)) -> Color {
+        let distance = |color: &Color| {
+            let (r, g, b) = color.rgb();
+
+            (r as i32 - rgb.0 as i32).pow(2)
+                + (g as i32 - rgb.1 as i32).pow(2)
+                + (b as i32 - rgb.2 as i32).pow(2)
+        };
+
+        Color::ALL
+            .into_iter()
+            .min_by_key(distance)
+            .unwrap_or(Color::Black)
+    }
+
+    /// Get a visually opposing color: the [`rgb`](Self::rgb) value of the color is inverted
+    /// (each component `c` becoming `255 - c`) and mapped back to the
+    /// [nearest](Self::nearest_ansi16) of the 16 colors. For instance, red gives bright cyan
+    /// and black gives bright white.
+    pub fn complement(&self) -> Color {
+        let (r, g, b) = self.rgb();
+
+        Color::nearest_ansi16((255 - r, 255 - g, 255 - b))
+    }
 }
 
 impl fmt::Debug for Color {
@@ -72,24 +203,151 @@ impl fmt::Display for Color {
     }
 }
 
+/// Horizontal alignment of a string within a wider field.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    Center,
+    Right,
+}
+
+impl Align {
+    /// Split `padding` into the amounts to put on the left and on the right of the content.
+    fn split(&self, padding: usize) -> (usize, usize) {
+        match self {
+            Align::Left => (0, padding),
+            Align::Center => (padding / 2, padding - padding / 2),
+            Align::Right => (padding, 0),
+        }
+    }
+}
+
+/// Text attribute, set by the matching setter such as [`ColoredString::set_bold`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Attribute {
+    Bold,
+    Faint,
+    Italic,
+    Underline,
+    SlowBlink,
+    FastBlink,
+    Reverse,
+    Conceal,
+    Strike,
+}
+
+impl Attribute {
+    /// Get the SGR code enabling the attribute.
+    pub fn code(&self) -> u8 {
+        match self {
+            Attribute::Bold => 1,
+            Attribute::Faint => 2,
+            Attribute::Italic => 3,
+            Attribute::Underline => 4,
+            Attribute::SlowBlink => 5,
+            Attribute::FastBlink => 6,
+            Attribute::Reverse => 7,
+            Attribute::Conceal => 8,
+            Attribute::Strike => 9,
+        }
+    }
+
+    /// Get the SGR code disabling the attribute. Bold and faint share 22, as do both blinks
+    /// with 25, disabling one disables the other as well.
+    pub fn disable_code(&self) -> u8 {
+        match self {
+            Attribute::Bold | Attribute::Faint => 22,
+            Attribute::SlowBlink | Attribute::FastBlink => 25,
+            _ => self.code() + 20,
+        }
+    }
+}
+
+/// What is reset by [`ColoredString::reset_with`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ResetMode {
+    /// Reset the colors and all the attributes, with SGR 0.
+    #[default]
+    Full,
+    /// Reset the foreground, background and underline colors only, keeping the attributes such
+    /// as bold or underline active.
+    Colors,
+}
+
+/// Error of the checked setters, such as [`ColoredString::try_set_fg_cube`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StyleError {
+    /// A color component or a font index above its maximum.
+    ComponentOutOfRange { value: u8, max: u8 },
+}
+
+impl fmt::Display for StyleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StyleError::ComponentOutOfRange { value, max } => {
+                write!(f, "color component {} out of 0..={}", value, max)
+            }
+        }
+    }
+}
+
+impl core::error::Error for StyleError {}
+
 /// Struct that indicates the position at which a ANSI code should be located in the colored
 /// string output.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
 struct CodeMarker {
     index: usize,
-    code: u8,
+    /// Parameters of the SGR sequence, there is always at least one.
+    codes: Vec<u8>,
+}
+
+impl CodeMarker {
+    /// Update the codes in effect with the ones of this marker, folding them so that the
+    /// overridden ones are left out and their number stays bounded.
+    fn apply(&self, active: &mut Vec<u8>) {
+        match self.codes.split_first() {
+            Some((0, codes)) => {
+                active.clear();
+                active.extend_from_slice(codes);
+            }
+            _ => active.extend_from_slice(&self.codes),
+        }
+
+        *active = fold_commands(active).concat();
+    }
+}
+
+impl fmt::Debug for CodeMarker {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, ", self.index)?;
+
+        for (i, code) in self.codes.iter().enumerate() {
+            if i > 0 {
+                write!(f, ";")?;
+            }
+
+            write!(f, "{}", code)?;
+        }
+
+        write!(f, ")")
+    }
 }
 
 /// Colored string builder.
+///
+/// Two colored strings are equal when both their text and their styling are. They are
+/// ordered by their raw text, the styling only telling apart strings with the same text.
+#[derive(Clone)]
 pub struct ColoredString {
     raw: String,
     code_markers: Vec<CodeMarker>,
+    /// Styles to restore by [`pop_style`](Self::pop_style), the innermost last.
+    style_stack: Vec<StyleSnapshot>,
 }
 
 const ANSI_ESCAPE_START: &str = "\x1b[";
 const ANSI_ESCAPE_END: &str = "m";
-const ANSI_ESCAPE_LEN: usize = ANSI_ESCAPE_START.len() +
-                               1 + // Color on one character.
-                               ANSI_ESCAPE_END.len();
 
 impl Default for ColoredString {
     fn default() -> Self {
@@ -97,12 +355,38 @@ impl Default for ColoredString {
     }
 }
 
+impl From<&str> for ColoredString {
+    /// Create a colored string holding `string`, with no styling.
+    fn from(string: &str) -> Self {
+        ColoredString::from(string.to_string())
+    }
+}
+
+impl From<String> for ColoredString {
+    /// Create a colored string holding `string`, with no styling.
+    fn from(string: String) -> Self {
+        ColoredString {
+            raw: string,
+            code_markers: Vec::new(),
+            style_stack: Vec::new(),
+        }
+    }
+}
+
+impl From<char> for ColoredString {
+    /// Create a colored string holding the `ch` character, with no styling.
+    fn from(ch: char) -> Self {
+        ColoredString::from(ch.to_string())
+    }
+}
+
 impl ColoredString {
     /// Create a new colored string.
     pub fn new() -> Self {
         ColoredString {
             raw: String::new(),
             code_markers: Vec::new(),
+            style_stack: Vec::new(),
         }
     }
 
@@ -111,13 +395,57 @@ impl ColoredString {
         ColoredString {
             raw: String::with_capacity(capacity),
             code_markers: Vec::new(),
+            style_stack: Vec::new(),
         }
     }
 
+    /// Create a colored string holding `text`, with no styling, builder style. An owned string
+    /// is taken as is, without copy.
+    pub fn with_text(text: impl Into<String>) -> ColoredString {
+        ColoredString::from(text.into())
+    }
+
+    /// Create a new colored string with specific capacities for the text, in bytes, and for
+    /// the codes, in number of escape sequences set.
+    pub fn with_capacities(text: usize, markers: usize) -> ColoredString {
+        ColoredString {
+            raw: String::with_capacity(text),
+            code_markers: Vec::with_capacity(markers),
+            style_stack: Vec::new(),
+        }
+    }
+
+    /// Get the number of bytes of text the string can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.raw.capacity()
+    }
+
+    /// Get the number of escape sequences the string can hold without reallocating.
+    pub fn marker_capacity(&self) -> usize {
+        self.code_markers.capacity()
+    }
+
     fn push_code(&mut self, code: u8) {
+        self.push_codes(&[code])
+    }
+
+    fn push_codes(&mut self, codes: &[u8]) {
+        if codes.is_empty() {
+            return;
+        }
+
+        // Setting the same codes twice in a row has no effect.
+        if self
+            .code_markers
+            .last()
+            .is_some_and(|last| last.index == self.raw.len() && last.codes == codes)
+        {
+            return;
+        }
+
         let color_marker = CodeMarker {
             index: self.raw.len(),
-            code,
+            codes: codes.to_vec(),
         };
 
         self.code_markers.push(color_marker)
@@ -127,19 +455,93 @@ impl ColoredString {
         self.push_code(base + color.int_value())
     }
 
-    fn push_format_code(&mut self, code: u8, enable: bool) {
-        let mut code_ = code;
+    fn push_format_code(&mut self, attribute: Attribute, enable: bool) {
+        if enable {
+            return self.push_code(attribute.code());
+        }
+
+        // The disabling code is shared by bold and faint, and by both blinks: the other one of
+        // the pair is enabled again if it was in effect.
+        let partner = match attribute {
+            Attribute::Bold => Some(Attribute::Faint),
+            Attribute::Faint => Some(Attribute::Bold),
+            Attribute::SlowBlink => Some(Attribute::FastBlink),
+            Attribute::FastBlink => Some(Attribute::SlowBlink),
+            _ => None,
+        };
 
-        if !enable {
-            code_ += 20
+        match partner {
+            Some(partner) if self.is_enabled(partner) => {
+                self.push_codes(&[attribute.disable_code(), partner.code()])
+            }
+            _ => self.push_code(attribute.disable_code()),
+        }
+    }
+
+    /// Check whether `attribute` is in effect at the end of the string. The codes are looked
+    /// at from the end, up to the last one changing the attribute.
+    fn is_enabled(&self, attribute: Attribute) -> bool {
+        for marker in self.code_markers.iter().rev() {
+            let mut enabled = None;
+
+            for command in sgr_commands(&marker.codes) {
+                match command {
+                    [0] => enabled = Some(false),
+                    [code] if *code == attribute.code() => enabled = Some(true),
+                    [code] if *code == attribute.disable_code() => enabled = Some(false),
+                    _ => (),
+                }
+            }
+
+            if let Some(enabled) = enabled {
+                return enabled;
+            }
         }
 
-        self.push_code(code_)
+        false
+    }
+
+    /// Insert an ANSI code at the byte `index` of the raw text. The markers are kept sorted by
+    /// index, a code inserted at an index already holding codes is placed after them.
+    ///
+    /// Panics if `index` is not on a `char` boundary of the raw text.
+    pub fn insert_code_at(&mut self, index: usize, code: u8) {
+        assert!(self.raw.is_char_boundary(index));
+
+        let position = self
+            .code_markers
+            .partition_point(|marker| marker.index <= index);
+
+        self.code_markers.insert(
+            position,
+            CodeMarker {
+                index,
+                codes: vec![code],
+            },
+        )
+    }
+
+    /// Set the given SGR parameters from this stage of the string, as a single escape sequence.
+    /// This gives access to the codes not covered by the other setters, such as the font
+    /// selection. No validation is performed, the codes are stored verbatim. As for the other
+    /// codes, the rendering drops the ones without effect, such as a color replaced by the
+    /// following one before any text.
+    pub fn push_raw_sgr(&mut self, codes: &[u8]) {
+        self.push_codes(codes)
     }
 
     /// Reset the formatting to the default on from this stage of the string.
     pub fn reset(&mut self) {
-        self.push_code(0)
+        self.reset_with(ResetMode::Full)
+    }
+
+    /// Reset the formatting from this stage of the string, either entirely or only the colors
+    /// depending on `mode`.
+    pub fn reset_with(&mut self, mode: ResetMode) {
+        match mode {
+            ResetMode::Full => self.push_code(0),
+            ResetMode::Colors => self.push_codes(&[39, 49, 59]),
+        }
     }
 
     /// Set the foreground color from this stage of the string.
@@ -152,34 +554,215 @@ impl ColoredString {
         self.push_color_code(color, 40);
     }
 
+    /// Set the bright variant of a foreground color from this stage of the string, with the
+    /// 90–97 codes: `set_fg_bright(&Color::Red)` is the same as `set_fg(&Color::BrightRed)`.
+    /// The bright colors are kept as is.
+    pub fn set_fg_bright(&mut self, color: &Color) {
+        self.push_code(90 + color.int_value() % 60);
+    }
+
+    /// Set the bright variant of a background color from this stage of the string, with the
+    /// 100–107 codes: `set_bg_bright(&Color::Blue)` is the same as
+    /// `set_bg(&Color::BrightBlue)`. The bright colors are kept as is.
+    pub fn set_bg_bright(&mut self, color: &Color) {
+        self.push_code(100 + color.int_value() % 60);
+    }
+
+    /// Select the font from this stage of the string: 0 is the primary font and 1 to 9 the
+    /// alternate ones, which only a few terminals support.
+    ///
+    /// Panics if `index` is greater than 9, see [`try_set_font`](Self::try_set_font) for
+    /// indexes coming from user input.
+    pub fn set_font(&mut self, index: u8) {
+        assert!(index <= 9, "font index {} out of 0..=9", index);
+
+        self.push_code(10 + index);
+    }
+
+    /// Select the font from this stage of the string, like [`set_font`](Self::set_font).
+    /// Nothing is set if `index` is out of `0..=9`.
+    pub fn try_set_font(&mut self, index: u8) -> Result<(), StyleError> {
+        self.push_code(10 + check_component(index, 9)?);
+        Ok(())
+    }
+
+    /// Set the foreground color from this stage of the string, as a 24-bit RGB value.
+    pub fn set_fg_rgb(&mut self, r: u8, g: u8, b: u8) {
+        self.push_codes(&[38, 2, r, g, b]);
+    }
+
+    /// Set the background color from this stage of the string, as a 24-bit RGB value.
+    pub fn set_bg_rgb(&mut self, r: u8, g: u8, b: u8) {
+        self.push_codes(&[48, 2, r, g, b]);
+    }
+
+    /// Set the foreground color from this stage of the string, as an index of the 256 colors
+    /// palette. See [`cube`] and [`gray`] to compute the index.
+    pub fn set_fg_256(&mut self, index: u8) {
+        self.push_codes(&[38, 5, index]);
+    }
+
+    /// Set the background color from this stage of the string, as an index of the 256 colors
+    /// palette. See [`cube`] and [`gray`] to compute the index.
+    pub fn set_bg_256(&mut self, index: u8) {
+        self.push_codes(&[48, 5, index]);
+    }
+
+    /// Set the foreground color from this stage of the string, from the 6×6×6 color cube of the
+    /// 256 colors palette. Nothing is set if a component is out of `0..=5`.
+    pub fn try_set_fg_cube(&mut self, r: u8, g: u8, b: u8) -> Result<(), StyleError> {
+        self.set_fg_256(cube(r, g, b)?);
+        Ok(())
+    }
+
+    /// Set the background color from this stage of the string, from the 6×6×6 color cube of the
+    /// 256 colors palette. Nothing is set if a component is out of `0..=5`.
+    pub fn try_set_bg_cube(&mut self, r: u8, g: u8, b: u8) -> Result<(), StyleError> {
+        self.set_bg_256(cube(r, g, b)?);
+        Ok(())
+    }
+
+    /// Set both the foreground and the background colors from this stage of the string. They
+    /// are rendered as a single escape sequence.
+    pub fn set_color(&mut self, fg: &Color, bg: &Color) {
+        self.set_fg(fg);
+        self.set_bg(bg);
+    }
+
+    /// Swap the foreground and background colors throughout the string, giving a reverse
+    /// video variant of it. The other attributes are left untouched.
+    pub fn invert_colors(&mut self) {
+        for marker in self.code_markers.iter_mut() {
+            let mut codes = Vec::with_capacity(marker.codes.len());
+
+            for command in sgr_commands(&marker.codes) {
+                let (first, args) = command.split_first().unwrap_or((&0, &[]));
+
+                codes.push(match first {
+                    30..=38 | 90..=97 => first + 10,
+                    40..=48 | 100..=107 => first - 10,
+                    39 => 49,
+                    49 => 39,
+                    _ => *first,
+                });
+                codes.extend_from_slice(args);
+            }
+
+            marker.codes = codes;
+        }
+    }
+
+    /// Turn the colors of the string into grays of the same [`luminance`]. The RGB colors are
+    /// converted directly, the 16 named colors through their [`Color::rgb`] value. The
+    /// 256-color palette ones are left untouched.
+    pub fn to_grayscale(&mut self) {
+        for marker in self.code_markers.iter_mut() {
+            let mut codes = Vec::with_capacity(marker.codes.len());
+
+            for command in sgr_commands(&marker.codes) {
+                let gray = |rgb| {
+                    let level = luminance(rgb);
+
+                    [2, level, level, level]
+                };
+
+                match command {
+                    [base @ (38 | 48), 2, r, g, b] => {
+                        codes.push(*base);
+                        codes.extend_from_slice(&gray((*r, *g, *b)));
+                    }
+                    [code] => match Color::from_code(*code) {
+                        Some(color) => {
+                            let background = matches!(code, 40..=47 | 100..=107);
+
+                            codes.push(if background { 48 } else { 38 });
+                            codes.extend_from_slice(&gray(color.rgb()));
+                        }
+                        None => codes.push(*code),
+                    },
+                    _ => codes.extend_from_slice(command),
+                }
+            }
+
+            marker.codes = codes;
+        }
+    }
+
     /// Enable or disable the text style to faint one from this stage of the string.
     pub fn set_faint(&mut self, enable: bool) {
-        self.push_format_code(1, enable)
+        self.push_format_code(Attribute::Faint, enable)
     }
 
     /// Enable or disable the text style to bold one from this stage of the string.
     pub fn set_bold(&mut self, enable: bool) {
-        self.push_format_code(2, enable)
+        self.push_format_code(Attribute::Bold, enable)
     }
 
     /// Enable or disable the text style to italic one from this stage of the string.
     pub fn set_italic(&mut self, enable: bool) {
-        self.push_format_code(3, enable)
+        self.push_format_code(Attribute::Italic, enable)
     }
 
     /// Enable or disable the text style to underline one from this stage of the string.
     pub fn set_underline(&mut self, enable: bool) {
-        self.push_format_code(4, enable)
+        self.push_format_code(Attribute::Underline, enable)
     }
 
     /// Enable or disable the text slow blinking from this stage of the string.
     pub fn set_slow_blink(&mut self, enable: bool) {
-        self.push_format_code(5, enable)
+        self.push_format_code(Attribute::SlowBlink, enable)
     }
 
     /// Enable or disable the text fast blinking from this stage of the string.
     pub fn set_fast_blink(&mut self, enable: bool) {
-        self.push_format_code(6, enable)
+        self.push_format_code(Attribute::FastBlink, enable)
+    }
+
+    /// Enable or disable the swapping of the foreground and background colors from this stage
+    /// of the string.
+    pub fn set_reverse(&mut self, enable: bool) {
+        self.push_format_code(Attribute::Reverse, enable)
+    }
+
+    /// Enable or disable the hiding of the text from this stage of the string.
+    pub fn set_conceal(&mut self, enable: bool) {
+        self.push_format_code(Attribute::Conceal, enable)
+    }
+
+    /// Enable or disable the text style to crossed-out one from this stage of the string.
+    pub fn set_strike(&mut self, enable: bool) {
+        self.push_format_code(Attribute::Strike, enable)
+    }
+
+    /// Disable a single attribute from this stage of the string, the rest of the styling
+    /// staying in effect, including the attribute sharing its
+    /// [disabling code](Attribute::disable_code).
+    pub fn reset_attribute(&mut self, attribute: Attribute) {
+        self.push_format_code(attribute, false)
+    }
+
+    /// Enable or disable the proportional spacing from this stage of the string. Its disabling
+    /// code is 50 rather than the usual enabling code plus 20.
+    pub fn set_proportional_spacing(&mut self, enable: bool) {
+        self.push_code(if enable { 26 } else { 50 })
+    }
+
+    /// Push a new line, the styling in effect being reset at the end of the previous line and
+    /// set again at the beginning of the new one. Pagers such as `less` reset the styling at new
+    /// lines, this keeps the output colored there, like
+    /// [`colored_multiline`](Self::colored_multiline) does at render time.
+    pub fn push_newline(&mut self) {
+        let active = self.active_codes_at(usize::MAX);
+
+        // Nothing to carry over when the codes cancel each other, such as a bold disabled.
+        if SgrState::from_codes(&active).is_default() {
+            self.raw.push('\n');
+            return;
+        }
+
+        self.push_code(0);
+        self.raw.push('\n');
+        self.push_codes(&fold_commands(&active).concat());
     }
 
     /// Push a character to the colored string.
@@ -188,58 +771,990 @@ impl ColoredString {
     }
 
     /// Push a string to the colored string.
+    ///
+    /// The string is expected to be free of control characters: an escape sequence in it would
+    /// be emitted as is and mess with the styling and the width of the output. Use
+    /// [`push_str_escaped`](Self::push_str_escaped) for untrusted input.
     pub fn push_str(&mut self, string: &str) {
         self.raw.push_str(string)
     }
 
+    /// Push ASCII bytes to the colored string, without the UTF-8 validation of a conversion to
+    /// `&str`. This is meant for hot paths producing ASCII output.
+    ///
+    /// # Safety
+    ///
+    /// `bytes` must only contain ASCII characters, otherwise the raw content could end up not
+    /// being valid UTF-8. This is only checked in debug builds.
+    pub unsafe fn push_ascii(&mut self, bytes: &[u8]) {
+        debug_assert!(bytes.is_ascii());
+
+        // SAFETY: ASCII bytes are valid UTF-8 on their own, as guaranteed by the caller.
+        unsafe { self.raw.as_mut_vec().extend_from_slice(bytes) }
+    }
+
+    /// Push a string which may be borrowed or owned to the colored string. An owned string is
+    /// reused as the raw content when there is none yet, instead of being copied.
+    pub fn push_cow(&mut self, string: Cow<str>) {
+        match string {
+            Cow::Owned(string) if self.raw.is_empty() => self.raw = string,
+            string => self.raw.push_str(&string),
+        }
+    }
+
+    /// Push the textual representation of any displayable value to the colored string.
+    pub fn push_display<T: fmt::Display>(&mut self, value: &T) {
+        // Writing into a `String` never fails.
+        let _ = fmt::Write::write_fmt(&mut self.raw, format_args!("{}", value));
+    }
+
+    /// Push a string to the colored string, replacing its control characters (other than new
+    /// lines and tabulations) by a visible representation so they can't inject codes in the
+    /// output. C0 controls and DEL are replaced by their Unicode control picture (`\x1b` becomes
+    /// `␛`), C1 controls by their `\u{..}` escape.
+    pub fn push_str_escaped(&mut self, string: &str) {
+        for ch in string.chars() {
+            match ch {
+                '\n' | '\t' => self.raw.push(ch),
+                '\0'..='\x1f' => self.raw.push(
+                    char::from_u32(0x2400 + ch as u32).unwrap_or(char::REPLACEMENT_CHARACTER),
+                ),
+                '\x7f' => self.raw.push('\u{2421}'),
+                '\u{80}'..='\u{9f}' => self.raw.extend(ch.escape_unicode()),
+                _ => self.raw.push(ch),
+            }
+        }
+    }
+
+    /// Append the content and the codes of another colored string as is.
+    fn append(&mut self, other: &ColoredString) {
+        let offset = self.raw.len();
+
+        for marker in other.code_markers.iter() {
+            self.code_markers.push(CodeMarker {
+                index: offset + marker.index,
+                codes: marker.codes.clone(),
+            });
+        }
+
+        self.raw.push_str(&other.raw);
+    }
+
+    /// Push another colored string, keeping its own styling: the current style is reset before
+    /// it and restored after it.
+    pub fn push_colored(&mut self, other: &ColoredString) {
+        if other.code_markers.is_empty() {
+            self.raw.push_str(&other.raw);
+            return;
+        }
+
+        if other.raw.is_empty() {
+            return;
+        }
+
+        let active = self.active_codes_at(usize::MAX);
+
+        if !active.is_empty() {
+            self.push_code(0);
+        }
+
+        self.append(other);
+        self.push_code(0);
+        self.push_codes(&active);
+    }
+
+    /// Replace the occurrences of `from` in the raw content by the colored string `with`,
+    /// which keeps its own styling as with [`push_colored`](Self::push_colored). The styling
+    /// around the occurrences is preserved, the codes set within them applying after the
+    /// replacement.
+    pub fn replace_styled(&mut self, from: &str, with: &ColoredString) {
+        if from.is_empty() {
+            return;
+        }
+
+        let mut ret = ColoredString::with_capacity(self.raw.len());
+        let mut markers = self.code_markers.iter().peekable();
+        let mut last = 0;
+
+        for (start, _) in self.raw.match_indices(from) {
+            let offset = ret.raw.len();
+
+            while let Some(marker) = markers.next_if(|marker| marker.index <= start) {
+                ret.code_markers.push(CodeMarker {
+                    index: offset + marker.index - last,
+                    codes: marker.codes.clone(),
+                });
+            }
+
+            ret.raw.push_str(&self.raw[last..start]);
+            ret.push_colored(with);
+            last = start + from.len();
+
+            while let Some(marker) = markers.next_if(|marker| marker.index < last) {
+                ret.push_codes(&marker.codes);
+            }
+        }
+
+        let offset = ret.raw.len();
+
+        for marker in markers {
+            ret.code_markers.push(CodeMarker {
+                index: offset + marker.index - last,
+                codes: marker.codes.clone(),
+            });
+        }
+
+        ret.raw.push_str(&self.raw[last..]);
+        self.raw = ret.raw;
+        self.code_markers = ret.code_markers;
+    }
+
+    /// Get the codes in effect at the byte `index` of the raw text, that is the ones set before
+    /// it and since the last reset, folded so that the overridden ones are left out.
+    fn active_codes_at(&self, index: usize) -> Vec<u8> {
+        let end = self
+            .code_markers
+            .partition_point(|marker| marker.index < index);
+        let markers = &self.code_markers[..end];
+        // The codes set before the last reset are not in effect anymore.
+        let start = markers
+            .iter()
+            .rposition(|marker| marker.codes.first() == Some(&0))
+            .unwrap_or(0);
+        let mut codes = Vec::new();
+
+        for marker in markers[start..].iter() {
+            marker.apply(&mut codes);
+        }
+
+        codes
+    }
+
+    /// Copy the `start..end` byte range of the raw content, along with its codes. The codes in
+    /// effect at `start` are set at the beginning of the copy.
+    fn slice(&self, start: usize, end: usize) -> ColoredString {
+        let mut ret = ColoredString::with_capacity(end - start);
+
+        ret.push_codes(&self.active_codes_at(start));
+
+        let first = self
+            .code_markers
+            .partition_point(|marker| marker.index < start);
+        let last = self
+            .code_markers
+            .partition_point(|marker| marker.index < end);
+
+        for marker in self.code_markers[first..last].iter() {
+            ret.code_markers.push(CodeMarker {
+                index: marker.index - start,
+                codes: marker.codes.clone(),
+            });
+        }
+
+        ret.raw.push_str(&self.raw[start..end]);
+
+        ret
+    }
+
+    /// Divide the string in two at the `char_index` character. Each half gets the codes of its
+    /// range, the second one starting with the style in effect at the split point. An index
+    /// past the end of the string gives an empty second half.
+    pub fn split_at(&self, char_index: usize) -> (ColoredString, ColoredString) {
+        let index = self
+            .raw
+            .char_indices()
+            .nth(char_index)
+            .map_or(self.raw.len(), |(index, _)| index);
+
+        (self.slice(0, index), self.slice(index, self.raw.len()))
+    }
+
+    /// Rebuild the string, letting `map` write the replacement of each character of the raw
+    /// content. The codes stay in front of the replacement of the character they preceded.
+    fn map_chars<F: FnMut(char, &mut String)>(&self, mut map: F) -> ColoredString {
+        let mut ret = ColoredString::with_capacity(self.raw.len());
+        let mut markers = self.code_markers.iter().peekable();
+
+        for (index, ch) in self.raw.char_indices() {
+            while let Some(marker) = markers.next_if(|marker| marker.index <= index) {
+                ret.push_codes(&marker.codes);
+            }
+
+            map(ch, &mut ret.raw);
+        }
+
+        for marker in markers {
+            ret.push_codes(&marker.codes);
+        }
+
+        ret
+    }
+
+    /// Get the string with its raw content in upper case, the codes staying attached to the
+    /// same characters.
+    pub fn to_uppercase(&self) -> ColoredString {
+        self.map_chars(|ch, raw| raw.extend(ch.to_uppercase()))
+    }
+
+    /// Get the string with its raw content in lower case, the codes staying attached to the
+    /// same characters.
+    pub fn to_lowercase(&self) -> ColoredString {
+        self.map_chars(|ch, raw| raw.extend(ch.to_lowercase()))
+    }
+
+    /// Get the string with each character replaced by `mask_char`, such as for a password, the
+    /// codes staying attached to the same characters. The control characters, such as the line
+    /// breaks, are kept. Unlike the [`Conceal`](Attribute::Conceal) attribute, the content is
+    /// hidden even on terminals ignoring it and when copied.
+    pub fn mask(&self, mask_char: char) -> ColoredString {
+        self.map_chars(|ch, raw| raw.push(if ch.is_control() { ch } else { mask_char }))
+    }
+
+    /// Transform the raw content as a whole with `f`, such as for a redaction. The codes are
+    /// kept in place when the transformed content has the same length in bytes and the codes
+    /// still fall on character boundaries; otherwise their positions are meaningless and they
+    /// are all dropped.
+    pub fn map_raw<F: FnOnce(String) -> String>(mut self, f: F) -> ColoredString {
+        let len = self.raw.len();
+
+        self.raw = f(core::mem::take(&mut self.raw));
+
+        if self.raw.len() != len
+            || !self
+                .code_markers
+                .iter()
+                .all(|marker| self.raw.is_char_boundary(marker.index))
+        {
+            self.code_markers.clear();
+        }
+
+        self
+    }
+
+    /// Get the string with its characters in reverse order, each one keeping its styling.
+    pub fn reverse_chars(&self) -> ColoredString {
+        let segments: Vec<(&str, Vec<u8>)> = self.segments().collect();
+        let mut ret = ColoredString::with_capacity(self.raw.len());
+        let mut active = Vec::new();
+
+        for (text, codes) in segments.into_iter().rev() {
+            if codes != active {
+                let mut switch = Vec::with_capacity(codes.len() + 1);
+
+                if !active.is_empty() {
+                    switch.push(0);
+                }
+
+                switch.extend_from_slice(&codes);
+                ret.push_codes(&switch);
+                active = codes;
+            }
+
+            ret.raw.extend(text.chars().rev());
+        }
+
+        ret
+    }
+
+    /// Replace the tabulations by the number of spaces needed to reach the next tab stop, tab
+    /// stops being every `tab_width` characters of a line. The codes stay in place, relative to
+    /// the text. A `tab_width` of 0 removes the tabulations.
+    pub fn expand_tabs(&self, tab_width: usize) -> ColoredString {
+        let mut column = 0;
+
+        self.map_chars(|ch, raw| match ch {
+            '\t' if tab_width > 0 => {
+                let spaces = tab_width - column % tab_width;
+
+                raw.extend(core::iter::repeat_n(' ', spaces));
+                column += spaces;
+            }
+            '\t' => (),
+            '\n' => {
+                raw.push(ch);
+                column = 0;
+            }
+            _ => {
+                raw.push(ch);
+                column += 1;
+            }
+        })
+    }
+
+    /// Wrap the string into lines of at most `width` visible characters, breaking only at
+    /// whitespace, unless a word is by itself wider than `width` in which case it is cut. The
+    /// new lines of the content are kept, the whitespace at the breaks is dropped. Each line
+    /// starts with the style in effect at its beginning.
+    pub fn wrap_words(&self, width: usize) -> Vec<ColoredString> {
+        let width = width.max(1);
+        let mut lines = Vec::new();
+        let mut paragraph_start = 0;
+
+        for paragraph in self.raw.split('\n') {
+            // Current line as (start, end, width).
+            let mut line: Option<(usize, usize, usize)> = None;
+
+            for (word_start, word) in words(paragraph) {
+                let mut start = paragraph_start + word_start;
+                let end = start + word.len();
+                let mut word_width = word.chars().count();
+
+                if let Some((line_start, line_end, line_width)) = line {
+                    let gap = self.raw[line_end..start].chars().count();
+
+                    if line_width + gap + word_width <= width {
+                        line = Some((line_start, end, line_width + gap + word_width));
+                        continue;
+                    }
+
+                    lines.push(self.slice(line_start, line_end));
+                }
+
+                while word_width > width {
+                    let cut = start
+                        + self.raw[start..]
+                            .char_indices()
+                            .nth(width)
+                            .map_or(0, |(index, _)| index);
+
+                    lines.push(self.slice(start, cut));
+                    start = cut;
+                    word_width -= width;
+                }
+
+                line = Some((start, end, word_width));
+            }
+
+            match line {
+                Some((line_start, line_end, _)) => lines.push(self.slice(line_start, line_end)),
+                None => lines.push(self.slice(paragraph_start, paragraph_start)),
+            }
+
+            paragraph_start += paragraph.len() + 1;
+        }
+
+        lines
+    }
+
+    /// Fit the string in `max_width` visible characters: a longer string is truncated and
+    /// ends with the uncolored `ellipsis`, the retained part keeping its styling. When
+    /// `max_width` is smaller than the ellipsis itself, only the truncated ellipsis is returned.
+    pub fn ellipsize(&self, max_width: usize, ellipsis: &str) -> ColoredString {
+        if self.width() <= max_width {
+            return self.clone();
+        }
+
+        let ellipsis_width = ellipsis.chars().count();
+
+        if ellipsis_width > max_width {
+            let mut ret = ColoredString::new();
+
+            ret.raw.extend(ellipsis.chars().take(max_width));
+
+            return ret;
+        }
+
+        let mut ret = self.clone();
+
+        ret.truncate(max_width - ellipsis_width);
+
+        if !ret.active_codes_at(usize::MAX).is_empty() {
+            ret.push_code(0);
+        }
+
+        ret.push_str(ellipsis);
+
+        ret
+    }
+
+    /// Get the lines of the string, each with its styling.
+    fn lines(&self) -> Vec<ColoredString> {
+        let mut lines = Vec::new();
+        let mut start = 0;
+
+        for line in self.raw.split('\n') {
+            lines.push(self.slice(start, start + line.len()));
+            start += line.len() + 1;
+        }
+
+        lines
+    }
+
+    /// Prepend `spaces` uncolored spaces to every line of the string. The styling in effect
+    /// at the end of a line is reset before the new line and restored after the indentation.
+    pub fn indent(&self, spaces: usize) -> ColoredString {
+        let lines = self.lines();
+        let mut ret = ColoredString::with_capacity(self.raw.len() + spaces * lines.len());
+
+        for (i, line) in lines.iter().enumerate() {
+            if i > 0 {
+                ret.push('\n');
+            }
+
+            ret.raw.extend(core::iter::repeat_n(' ', spaces));
+            ret.push_colored(line);
+        }
+
+        ret
+    }
+
+    /// Frame the string in a box drawn with Unicode box-drawing characters colored with
+    /// `color`. The lines are padded so the borders are aligned.
+    pub fn boxed(&self, color: &Color) -> ColoredString {
+        let lines = self.lines();
+        let width = lines.iter().map(ColoredString::width).max().unwrap_or(0);
+        let mut ret = ColoredString::with_capacity((width + 4) * (lines.len() + 2) * 3);
+        let border = |ret: &mut ColoredString, text: &str| {
+            ret.set_fg(color);
+            ret.push_str(text);
+            ret.reset();
+        };
+
+        border(&mut ret, &format!("┌{}┐", "─".repeat(width + 2)));
+        ret.push('\n');
+
+        for line in lines {
+            border(&mut ret, "│");
+            ret.push(' ');
+            ret.push_colored(&line.ljust(width, ' '));
+            ret.push(' ');
+            border(&mut ret, "│");
+            ret.push('\n');
+        }
+
+        border(&mut ret, &format!("└{}┘", "─".repeat(width + 2)));
+
+        ret
+    }
+
+    /// Shorten the raw content to `len` bytes, dropping the codes which only applied to the
+    /// removed part.
+    fn truncate_bytes(&mut self, len: usize) {
+        if len >= self.raw.len() {
+            return;
+        }
+
+        self.raw.truncate(len);
+        self.code_markers.retain(|marker| marker.index < len);
+    }
+
+    /// Keep only the first `width` characters of the string.
+    pub fn truncate(&mut self, width: usize) {
+        if let Some((index, _)) = self.raw.char_indices().nth(width) {
+            self.truncate_bytes(index)
+        }
+    }
+
+    /// Keep only the last `width` characters of the string, the style in effect at the new
+    /// beginning being set there.
+    pub fn truncate_start(&mut self, width: usize) {
+        let Some(count) = self.raw.chars().count().checked_sub(width) else {
+            return;
+        };
+        let start = self
+            .raw
+            .char_indices()
+            .nth(count)
+            .map_or(self.raw.len(), |(index, _)| index);
+        let active = self.active_codes_at(start);
+        let markers = core::mem::take(&mut self.code_markers);
+
+        self.raw.drain(..start);
+
+        if !active.is_empty() {
+            self.code_markers.push(CodeMarker {
+                index: 0,
+                codes: active,
+            });
+        }
+
+        self.code_markers.extend(
+            markers
+                .into_iter()
+                .filter(|marker| marker.index >= start)
+                .map(|marker| CodeMarker {
+                    index: marker.index - start,
+                    codes: marker.codes,
+                }),
+        );
+    }
+
+    /// Keep only the first grapheme clusters of the string fitting in `cells` terminal cells. A
+    /// wide character or a combining sequence is either kept or dropped as a whole, never split.
+    #[cfg(all(feature = "unicode-width", feature = "unicode-segmentation"))]
+    pub fn truncate_width(&mut self, cells: usize) {
+        use unicode_segmentation::UnicodeSegmentation;
+
+        let mut width = 0;
+
+        for (index, grapheme) in self.raw.grapheme_indices(true) {
+            width += unicode_width::UnicodeWidthStr::width(grapheme);
+
+            if width > cells {
+                self.truncate_bytes(index);
+                return;
+            }
+        }
+    }
+
     /// Get the raw content of the string without colors or any formatting.
     #[inline]
     pub fn raw(&self) -> String {
         self.raw.clone()
     }
 
+    /// Get the raw content of the string without colors or any formatting, borrowed.
+    #[inline]
+    pub fn raw_str(&self) -> &str {
+        &self.raw
+    }
+
+    /// Iterate over the characters of the raw content.
+    pub fn chars(&self) -> core::str::Chars<'_> {
+        self.raw.chars()
+    }
+
+    /// Iterate over the characters of the raw content, along with their byte index.
+    pub fn char_indices(&self) -> core::str::CharIndices<'_> {
+        self.raw.char_indices()
+    }
+
+    /// Get the byte index of the first occurrence of `pat` in the raw content, styling ignored.
+    pub fn find(&self, pat: &str) -> Option<usize> {
+        self.raw.find(pat)
+    }
+
+    /// Check whether the raw content contains `needle`, styling ignored: a needle spanning
+    /// several styled runs is found as long as it is contiguous in the text.
+    pub fn contains(&self, needle: &str) -> bool {
+        self.raw.contains(needle)
+    }
+
+    /// Check whether the raw content starts with `pat`, styling ignored.
+    pub fn starts_with(&self, pat: &str) -> bool {
+        self.raw.starts_with(pat)
+    }
+
+    /// Check whether the raw content ends with `pat`, styling ignored.
+    pub fn ends_with(&self, pat: &str) -> bool {
+        self.raw.ends_with(pat)
+    }
+
+    /// Iterate over the runs of text of the string, along with the SGR codes in effect on
+    /// them.
+    pub fn segments(&self) -> Segments<'_> {
+        Segments::new(self)
+    }
+
+    /// Check whether the output is colored, that is whether [`colored`](Self::colored)
+    /// differs from [`raw`](Self::raw). The codes without effect, such as a reset of the
+    /// default styling, do not count. This is cheaper than comparing both, nothing being
+    /// allocated.
+    pub fn has_color(&self) -> bool {
+        let mut parts = LazyColored::new(self);
+
+        while let Some(part) = parts.next() {
+            if !parts.is_text(part) {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Iterate over the codes set in the string, in order, as the byte index of the raw text
+    /// they are set at along with their SGR parameters. This gives everything needed to render
+    /// the string with another backend.
+    pub fn markers(&self) -> impl Iterator<Item = (usize, &[u8])> {
+        self.code_markers
+            .iter()
+            .map(|marker| (marker.index, marker.codes.as_slice()))
+    }
+
+    /// Get the SGR parameters of each escape sequence set in the string, in order.
+    pub fn sgr_codes(&self) -> Vec<Vec<u8>> {
+        self.code_markers
+            .iter()
+            .map(|marker| marker.codes.clone())
+            .collect()
+    }
+
+    /// Get the string without any styling, for output that doesn't support colors. This is the
+    /// same as [`raw`](Self::raw), under a name stating the intent.
+    pub fn plain(&self) -> String {
+        self.raw()
+    }
+
+    /// Get the string without its colors. For now all the styling is dropped, as with
+    /// [`plain`](Self::plain), but this is reserved to keep the non-color structure (such as
+    /// bold or underline) in the future.
+    pub fn monochrome(&self) -> String {
+        self.raw()
+    }
+
+    /// Get the number of `char` in the raw content, which is the width of the string on the
+    /// terminal as long as it is made of single cell characters.
+    pub fn width(&self) -> usize {
+        self.raw.chars().count()
+    }
+
+    /// Pad the string with the `fill` character up to `width` visible characters, aligning the
+    /// content as requested. The padding is never styled. A string already wider than `width`
+    /// is returned unchanged.
+    pub fn pad_to(&self, width: usize, align: Align, fill: char) -> ColoredString {
+        let (left, right) = align.split(width.saturating_sub(self.width()));
+        let mut ret =
+            ColoredString::with_capacity(self.raw.len() + (left + right) * fill.len_utf8());
+
+        ret.raw.extend(core::iter::repeat_n(fill, left));
+
+        ret.append(self);
+
+        if right > 0 {
+            if !self.code_markers.is_empty() {
+                ret.push_code(0);
+            }
+
+            ret.raw.extend(core::iter::repeat_n(fill, right));
+        }
+
+        ret
+    }
+
+    /// Center the string in a field of `width` visible characters, see [`pad_to`](Self::pad_to).
+    pub fn center(&self, width: usize, fill: char) -> ColoredString {
+        self.pad_to(width, Align::Center, fill)
+    }
+
+    /// Left-justify the string in a field of `width` visible characters, see
+    /// [`pad_to`](Self::pad_to).
+    pub fn ljust(&self, width: usize, fill: char) -> ColoredString {
+        self.pad_to(width, Align::Left, fill)
+    }
+
+    /// Right-justify the string in a field of `width` visible characters, see
+    /// [`pad_to`](Self::pad_to).
+    pub fn rjust(&self, width: usize, fill: char) -> ColoredString {
+        self.pad_to(width, Align::Right, fill)
+    }
+
+    /// Get the number of terminal cells taken by the raw content. Wide characters, such as
+    /// CJK ideograms and emoji, count as 2 while zero-width ones, such as combining marks,
+    /// count as 0.
+    #[cfg(feature = "unicode-width")]
+    pub fn display_width(&self) -> usize {
+        unicode_width::UnicodeWidthStr::width(self.raw.as_str())
+    }
+
     /// Get the colored string. The colored output will always be so the colors
-    /// are reset at the end of the string.
+    /// are reset at the end of the string, a final reset being added unless the styling is
+    /// already back to the default one.
+    ///
+    /// When there is no text, nothing is to be styled and an empty string is returned, whatever
+    /// the codes set.
+    ///
+    /// The codes which would not change the styling in effect, such as setting again the
+    /// current foreground color, are not emitted, neither are the ones overridden by codes
+    /// set right after them, such as disabling bold and enabling it again with no text in
+    /// between. The string is considered to start with the default styling.
     pub fn colored(&self) -> String {
-        if self.code_markers.is_empty() {
-            return self.raw();
-        }
+        let mut ret = String::with_capacity(self.colored_capacity());
 
-        let mut index: usize = 0;
+        ret.extend(self.render_parts());
+
+        ret
+    }
+
+    /// Get the [`colored`](Self::colored) output preceded by a reset, so that no styling left
+    /// active by a previous output bleeds into this one, such as when independent strings are
+    /// concatenated.
+    pub fn colored_with_leading_reset(&self) -> String {
         let mut ret = String::with_capacity(
-            self.raw.len() + ((self.code_markers.len() + 1) * ANSI_ESCAPE_LEN),
+            ANSI_ESCAPE_START.len() + 1 + ANSI_ESCAPE_END.len() + self.colored_capacity(),
         );
 
-        for color_marker in self.code_markers.iter() {
-            if ret.is_empty() {
-                ret += &self.raw[index..color_marker.index];
-                ret += ANSI_ESCAPE_START;
-            } else if index != color_marker.index {
-                ret += ANSI_ESCAPE_END;
-                ret += &self.raw[index..color_marker.index];
-                ret += ANSI_ESCAPE_START;
+        ret.push_str(ANSI_ESCAPE_START);
+        ret.push('0');
+        ret.push_str(ANSI_ESCAPE_END);
+        ret.extend(self.render_parts());
+        ret
+    }
+
+    /// Get the bytes of the colored string like [`colored`](Self::colored), the escape
+    /// sequences starting with the single 8-bit CSI byte (0x9B) instead of `ESC [` when
+    /// `csi_8bit` is set. The output is then not valid UTF-8, as the terminals accepting the
+    /// 8-bit introducer expect. The text itself stays encoded in UTF-8.
+    pub fn colored_with_introducer(&self, csi_8bit: bool) -> Vec<u8> {
+        if !csi_8bit {
+            return self.colored().into_bytes();
+        }
+
+        let mut parts = LazyColored::new(self).with_introducer("\u{9b}");
+        let mut ret = Vec::with_capacity(self.colored_capacity());
+
+        while let Some(part) = parts.next() {
+            if parts.is_text(part) {
+                ret.extend_from_slice(part.as_bytes());
             } else {
-                ret.push(';')
+                // The escape sequences are ASCII but for the introducer, U+009B, which is
+                // written as the byte of the same value rather than encoded in UTF-8.
+                ret.extend(part.chars().map(|ch| ch as u8));
             }
+        }
+
+        ret
+    }
+
+    /// Get the [`colored`](Self::colored) output preceded by the OSC 2 escape sequence setting
+    /// the title of the terminal window to `title`. The control characters of the title are
+    /// dropped so they cannot end the sequence early.
+    pub fn with_window_title(&self, title: &str) -> String {
+        let mut ret = String::with_capacity(title.len() + 5 + self.colored_capacity());
+
+        ret.push_str("\x1b]2;");
+        ret.extend(title.chars().filter(|ch| !ch.is_control()));
+        ret.push('\x07');
+        ret.extend(self.render_parts());
 
-            ret += &color_marker.code.to_string();
+        ret
+    }
+
+    /// Get the length in bytes of the [`colored`](Self::colored) output, without building it.
+    /// The codes without effect being skipped, this runs the rendering without writing it.
+    pub fn colored_len(&self) -> usize {
+        self.render_parts().map(str::len).sum()
+    }
 
-            index = color_marker.index;
+    /// Get a capacity fitting the [`colored`](Self::colored) output, without rendering it: each
+    /// code counted as three digits and a separator, each marker as an escape sequence of its
+    /// own, plus the final reset. The codes skipped by the rendering make it an upper bound.
+    fn colored_capacity(&self) -> usize {
+        let escapes: usize = self
+            .code_markers
+            .iter()
+            .map(|marker| ANSI_ESCAPE_START.len() + ANSI_ESCAPE_END.len() + 4 * marker.codes.len())
+            .sum();
+
+        self.raw.len() + escapes + ANSI_ESCAPE_START.len() + 1 + ANSI_ESCAPE_END.len()
+    }
+
+    /// Get the number of escape sequences of the [`colored`](Self::colored) output, the final
+    /// reset included, without building it.
+    pub fn escape_count(&self) -> usize {
+        let mut parts = self.render_parts();
+
+        parts.by_ref().for_each(drop);
+        parts.escapes()
+    }
+
+    /// Get the colored string as an iterator over its parts, escape sequences and text, without
+    /// building the whole output. This allows to stream it to a writer without allocation.
+    pub fn render_parts(&self) -> LazyColored<'_> {
+        LazyColored::new(self)
+    }
+
+    /// Get the colored string, with each line styled on its own: the codes in effect are set
+    /// again at the beginning of every line and reset at its end. Pagers such as `less` reset
+    /// the styling at new lines, this keeps multi-line output colored there.
+    pub fn colored_multiline(&self) -> String {
+        let mut ret = String::with_capacity(self.raw.len());
+        let mut start = 0;
+
+        for line in self.raw.split('\n') {
+            if start > 0 {
+                ret.push('\n');
+            }
+
+            ret += &self.slice(start, start + line.len()).colored();
+            start += line.len() + 1;
         }
 
-        ret += ANSI_ESCAPE_END;
-        ret += &self.raw[index..self.raw.len()];
+        ret
+    }
+}
 
-        ret += ANSI_ESCAPE_START;
-        ret.push('0');
-        ret += ANSI_ESCAPE_END;
+/// Check that a color component is at most `max`.
+fn check_component(value: u8, max: u8) -> Result<u8, StyleError> {
+    if value > max {
+        return Err(StyleError::ComponentOutOfRange { value, max });
+    }
+
+    Ok(value)
+}
+
+/// Get the index in the 256 colors palette of a color of the 6×6×6 cube, each component being
+/// in `0..=5`.
+pub fn cube(r: u8, g: u8, b: u8) -> Result<u8, StyleError> {
+    Ok(16 + 36 * check_component(r, 5)? + 6 * check_component(g, 5)? + check_component(b, 5)?)
+}
+
+/// Get the index in the 256 colors palette of a shade of the grayscale ramp, from 0 (darkest)
+/// to 23 (lightest).
+pub fn gray(level: u8) -> Result<u8, StyleError> {
+    Ok(232 + check_component(level, 23)?)
+}
+
+/// Round a non-negative value to the nearest integer, `f32::round` needing `std`.
+fn round(value: f32) -> usize {
+    (value + 0.5) as usize
+}
+
+/// Get the perceived lightness of a RGB color, from 0 to 255, using the Rec. 709 luma
+/// coefficients.
+pub fn luminance(rgb: (u8, u8, u8)) -> u8 {
+    round(0.2126 * rgb.0 as f32 + 0.7152 * rgb.1 as f32 + 0.0722 * rgb.2 as f32) as u8
+}
+
+/// Get a color for `key`, always the same for a given key, across runs and platforms. This
+/// allows to color consistently things such as log sources or user names.
+pub fn color_for(key: &str) -> Color {
+    color_for_excluding(key, &[])
+}
+
+/// Same as [`color_for`], never giving one of the `excluded` colors, such as the ones hard to
+/// read on the terminal background. If all the colors are excluded, the exclusion is ignored.
+pub fn color_for_excluding(key: &str, excluded: &[Color]) -> Color {
+    // FNV-1a, which unlike the standard library hasher is stable.
+    let hash = key.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+
+    let mut candidates: Vec<Color> = Color::ALL
+        .into_iter()
+        .filter(|color| !excluded.contains(color))
+        .collect();
+
+    if candidates.is_empty() {
+        candidates = Color::ALL.to_vec();
+    }
+
+    candidates[(hash % candidates.len() as u64) as usize]
+}
+
+/// Iterate over the whitespace separated words of `text`, along with their byte index.
+fn words(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    text.split(char::is_whitespace)
+        .filter(|word| !word.is_empty())
+        .map(move |word| (word.as_ptr() as usize - text.as_ptr() as usize, word))
+}
+
+/// Concatenate colored strings, placing the `sep` separator between each of them.
+pub fn join(parts: &[ColoredString], sep: &str) -> ColoredString {
+    let mut ret = ColoredString::new();
+
+    for (i, part) in parts.iter().enumerate() {
+        if i > 0 {
+            ret.push_str(sep);
+        }
+
+        ret.push_colored(part);
+    }
+
+    ret
+}
+
+/// Concatenate colored strings, placing the colored `sep` separator between each of them.
+pub fn join_colored(parts: &[ColoredString], sep: &ColoredString) -> ColoredString {
+    let mut ret = ColoredString::new();
+
+    for (i, part) in parts.iter().enumerate() {
+        if i > 0 {
+            ret.push_colored(sep);
+        }
 
+        ret.push_colored(part);
+    }
+
+    ret
+}
+
+/// Color each line of `lines` in its entirety, with the foreground color picked by `color_fn`
+/// for the content of the line.
+pub fn colorize_lines<I: Iterator<Item = String>>(
+    lines: I,
+    mut color_fn: impl FnMut(&str) -> Color,
+) -> impl Iterator<Item = ColoredString> {
+    lines.map(move |line| {
+        let mut ret = ColoredString::new();
+
+        ret.set_fg(&color_fn(&line));
+        ret.push_str(&line);
         ret
+    })
+}
+
+impl fmt::Debug for ColoredString {
+    /// Show the raw text and the `(index, code)` markers, without emitting any escape sequence.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ColoredString")
+            .field("raw", &self.raw)
+            .field("markers", &self.code_markers)
+            .finish()
+    }
+}
+
+impl PartialOrd for ColoredString {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for ColoredString {
+    fn eq(&self, other: &Self) -> bool {
+        self.raw == other.raw && self.code_markers == other.code_markers
+    }
+}
+
+impl Eq for ColoredString {}
+
+impl Ord for ColoredString {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.raw
+            .cmp(&other.raw)
+            .then_with(|| self.code_markers.cmp(&other.code_markers))
+    }
+}
+
+impl fmt::Write for ColoredString {
+    /// Push a string to the colored string, so `write!` can be used to build it.
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push_str(s);
+        Ok(())
     }
 }
 
 impl fmt::Display for ColoredString {
+    /// Write the colored string, part by part without building the whole output. The width,
+    /// alignment and fill of the formatter are honored based on the visible width of the
+    /// string, the padding being put outside of the escapes. The width is measured in terminal
+    /// cells with the `unicode-width` feature, in characters otherwise.
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.colored())
+        let Some(width) = f.width() else {
+            return self.render_parts().try_for_each(|part| f.write_str(part));
+        };
+
+        let align = match f.align() {
+            Some(fmt::Alignment::Center) => Align::Center,
+            Some(fmt::Alignment::Right) => Align::Right,
+            _ => Align::Left,
+        };
+        #[cfg(feature = "unicode-width")]
+        let visible = self.display_width();
+        #[cfg(not(feature = "unicode-width"))]
+        let visible = self.width();
+        let (left, right) = align.split(width.saturating_sub(visible));
+        let fill = f.fill();
+
+        for _ in 0..left {
+            write!(f, "{}", fill)?;
+        }
+
+        self.render_parts().try_for_each(|part| f.write_str(part))?;
+
+        for _ in 0..right {
+            write!(f, "{}", fill)?;
+        }
+
+        Ok(())
     }
 }
@@ -0,0 +1,286 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use core::fmt;
+
+use crate::sgr::fold_commands;
+use crate::{Color, ColoredString};
+
+/// Error found while parsing a markup with [`ColoredString::from_markup`]. The indexes are
+/// byte indexes in the markup.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MarkupError {
+    /// A `[` without the `]` ending the tag.
+    UnterminatedTag { index: usize },
+    /// A tag whose name is neither a color, an attribute nor SGR parameters.
+    UnknownTag { index: usize, name: String },
+    /// A closing tag while no tag is opened.
+    UnexpectedClose { index: usize },
+    /// A closing tag whose name is not the one of the last opened tag.
+    MismatchedTag {
+        index: usize,
+        expected: String,
+        found: String,
+    },
+    /// A tag still opened at the end of the markup.
+    UnclosedTag { index: usize, name: String },
+}
+
+impl fmt::Display for MarkupError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MarkupError::UnterminatedTag { index } => {
+                write!(f, "unterminated tag at index {}", index)
+            }
+            MarkupError::UnknownTag { index, name } => {
+                write!(f, "unknown tag [{}] at index {}", name, index)
+            }
+            MarkupError::UnexpectedClose { index } => {
+                write!(f, "closing tag without opened tag at index {}", index)
+            }
+            MarkupError::MismatchedTag {
+                index,
+                expected,
+                found,
+            } => write!(
+                f,
+                "closing tag [/{}] at index {} does not match the opened tag [{}]",
+                found, index, expected
+            ),
+            MarkupError::UnclosedTag { index, name } => {
+                write!(f, "tag [{}] at index {} is never closed", name, index)
+            }
+        }
+    }
+}
+
+impl core::error::Error for MarkupError {}
+
+/// Names of the attribute tags, along with their SGR code.
+const ATTRIBUTES: [(&str, u8); 8] = [
+    ("bold", 1),
+    ("faint", 2),
+    ("italic", 3),
+    ("underline", 4),
+    ("blink", 5),
+    ("reverse", 7),
+    ("conceal", 8),
+    ("strike", 9),
+];
+
+/// Get the snake case name of a color, such as `bright_red`.
+fn color_name(color: &Color) -> String {
+    color.to_string().to_lowercase().replace(' ', "_")
+}
+
+/// Get the name of the tag applying a SGR command.
+fn tag_name(command: &[u8]) -> String {
+    match command {
+        [code @ (30..=37 | 90..=97)] => Color::from_code(*code).map(|color| color_name(&color)),
+        [code @ (40..=47 | 100..=107)] => {
+            Color::from_code(*code).map(|color| format!("on_{}", color_name(&color)))
+        }
+        [code] => ATTRIBUTES
+            .iter()
+            .find(|(_, attribute)| attribute == code)
+            .map(|(name, _)| name.to_string()),
+        _ => None,
+    }
+    .unwrap_or_else(|| {
+        command
+            .iter()
+            .map(|code| code.to_string())
+            .collect::<Vec<_>>()
+            .join(";")
+    })
+}
+
+/// Get the SGR parameters applied by the tag `name`, the reverse of [`tag_name`].
+fn tag_codes(name: &str) -> Option<Vec<u8>> {
+    let color = |name: &str| {
+        Color::ALL
+            .into_iter()
+            .find(|color| color_name(color) == name)
+    };
+
+    if let Some(color) = color(name) {
+        return Some(vec![30 + color.int_value()]);
+    }
+
+    if let Some(color) = name.strip_prefix("on_").and_then(color) {
+        return Some(vec![40 + color.int_value()]);
+    }
+
+    if let Some((_, code)) = ATTRIBUTES.iter().find(|(attribute, _)| *attribute == name) {
+        return Some(vec![*code]);
+    }
+
+    name.split(';')
+        .map(|code| code.parse().ok())
+        .collect::<Option<Vec<u8>>>()
+}
+
+/// Push `text` to `out`, escaping the characters meaningful to the markup.
+fn push_escaped(out: &mut String, text: &str) {
+    for ch in text.chars() {
+        if matches!(ch, '[' | ']' | '\\') {
+            out.push('\\');
+        }
+
+        out.push(ch);
+    }
+}
+
+impl ColoredString {
+    /// Get the bracketed markup representation of the string, such as `[red]error[/red] ok`:
+    /// each SGR command in effect on a piece of text becomes a tag around it. The tag is closed
+    /// where the command stops being in effect, such as when the attribute is disabled or the
+    /// color replaced.
+    ///
+    /// The grammar is the following:
+    /// - `[name]` opens a tag, styling the text up to its closing tag. The names are the
+    ///   colors in snake case (`red`, `bright_red`), the background colors prefixed with `on_`
+    ///   (`on_blue`), the attributes (`bold`, `faint`, `italic`, `underline`, `blink`,
+    ///   `reverse`, `conceal`, `strike`), or any SGR command as its parameters separated by
+    ///   `;` (`38;5;40`).
+    /// - `[/name]` closes the last opened tag, which must be `name`, and `[/]` closes the last
+    ///   opened tag whatever its name. Closing a tag restores the styling of the tags still
+    ///   opened.
+    /// - `\[`, `\]` and `\\` are the literal `[`, `]` and `\` characters.
+    pub fn to_markup(&self) -> String {
+        let mut ret = String::with_capacity(self.raw.len());
+        let mut opened: Vec<String> = Vec::new();
+
+        for (text, codes) in self.segments() {
+            let tags: Vec<String> = fold_commands(&codes).into_iter().map(tag_name).collect();
+            let common = opened
+                .iter()
+                .zip(tags.iter())
+                .take_while(|(opened, tag)| opened == tag)
+                .count();
+
+            for tag in opened.drain(common..).rev() {
+                ret.push_str(&format!("[/{}]", tag));
+            }
+
+            for tag in &tags[common..] {
+                ret.push_str(&format!("[{}]", tag));
+            }
+
+            opened = tags;
+            push_escaped(&mut ret, text);
+        }
+
+        for tag in opened.iter().rev() {
+            ret.push_str(&format!("[/{}]", tag));
+        }
+
+        ret
+    }
+
+    /// Parse a markup as produced by [`to_markup`](Self::to_markup), following the grammar
+    /// described there. For instance, `[red][bold]hi[/][/] ok` gives `hi` in bold red followed
+    /// by ` ok` without styling.
+    ///
+    /// The round-trip through [`to_markup`](Self::to_markup) keeps the styling of each
+    /// character, as reported by [`char_colors`](Self::char_colors), but not necessarily the
+    /// codes: closing a tag resets the styling and sets again the tags still opened, so a bold
+    /// disabled with `22` comes back as `0` followed by the other codes in effect.
+    pub fn from_markup(s: &str) -> Result<ColoredString, MarkupError> {
+        let mut ret = ColoredString::with_capacity(s.len());
+        // Opened tags, with their index in the markup, name and codes.
+        let mut opened: Vec<(usize, &str, Vec<u8>)> = Vec::new();
+        // Number of opened tags whose codes are set in the output.
+        let mut applied = 0;
+        // Whether a closed tag is still in effect in the output.
+        let mut stale = false;
+        let mut text = String::new();
+        let mut index = 0;
+
+        while let Some(ch) = s[index..].chars().next() {
+            match ch {
+                '\\' => {
+                    let escaped = s[index + 1..].chars().next().unwrap_or('\\');
+
+                    text.push(escaped);
+                    index += 1 + escaped.len_utf8().min(s.len() - index - 1);
+                    continue;
+                }
+                '[' => (),
+                _ => {
+                    text.push(ch);
+                    index += ch.len_utf8();
+                    continue;
+                }
+            }
+
+            let Some(len) = s[index..].find(']') else {
+                return Err(MarkupError::UnterminatedTag { index });
+            };
+            let tag = &s[index + 1..index + len];
+
+            if !text.is_empty() {
+                ret.push_str(&text);
+                text.clear();
+            }
+
+            if let Some(name) = tag.strip_prefix('/') {
+                let Some((_, last, _)) = opened.last() else {
+                    return Err(MarkupError::UnexpectedClose { index });
+                };
+
+                if !name.is_empty() && name != *last {
+                    return Err(MarkupError::MismatchedTag {
+                        index,
+                        expected: last.to_string(),
+                        found: name.to_string(),
+                    });
+                }
+
+                opened.pop();
+                stale |= applied > opened.len();
+                applied = applied.min(opened.len());
+            } else {
+                let Some(codes) = tag_codes(tag) else {
+                    return Err(MarkupError::UnknownTag {
+                        index,
+                        name: tag.to_string(),
+                    });
+                };
+
+                opened.push((index, tag, codes));
+            }
+
+            index += len + 1;
+
+            // Set the codes of the tags only once there is text to style.
+            if s[index..].is_empty() || s[index..].starts_with('[') {
+                continue;
+            }
+
+            let mut codes = Vec::new();
+
+            if core::mem::take(&mut stale) {
+                codes.push(0);
+                applied = 0;
+            }
+
+            for (_, _, tag_codes) in &opened[applied..] {
+                codes.extend_from_slice(tag_codes);
+            }
+
+            applied = opened.len();
+            ret.push_codes(&codes);
+        }
+
+        if let Some((index, name, _)) = opened.pop() {
+            return Err(MarkupError::UnclosedTag {
+                index,
+                name: name.to_string(),
+            });
+        }
+
+        ret.push_str(&text);
+        Ok(ret)
+    }
+}
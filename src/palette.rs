@@ -0,0 +1,126 @@
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+
+use crate::{Color, ColoredString};
+
+/// Set of semantic color names, such as `"error"` or `"warn"`, resolved to colors at render
+/// time. This allows an application to define its theme in one place. The RGB values the
+/// colors are rendered with are set by [`set_rgb_palette`](crate::set_rgb_palette).
+#[derive(Clone, Debug, Default)]
+pub struct Palette {
+    colors: BTreeMap<String, Color>,
+}
+
+impl Palette {
+    /// Create an empty palette.
+    pub fn new() -> Self {
+        Palette {
+            colors: BTreeMap::new(),
+        }
+    }
+
+    /// Add a named color to the palette, builder style.
+    pub fn with(mut self, name: &str, color: Color) -> Self {
+        self.insert(name, color);
+        self
+    }
+
+    /// Add or replace a named color of the palette.
+    pub fn insert(&mut self, name: &str, color: Color) {
+        self.colors.insert(name.to_string(), color);
+    }
+
+    /// Get the color associated to a name.
+    pub fn get(&self, name: &str) -> Option<Color> {
+        self.colors.get(name).copied()
+    }
+
+    /// Get the color of a log level: the one associated to its [name](LogLevel::name) if any,
+    /// the default [`level_color`] otherwise.
+    pub fn level_color(&self, level: LogLevel) -> Color {
+        self.get(level.name()).unwrap_or(level_color(level))
+    }
+
+    /// Get `text` with the foreground color of a log level, see
+    /// [`level_color`](Self::level_color).
+    pub fn style_level(&self, level: LogLevel, text: &str) -> ColoredString {
+        level_text(self.level_color(level), text)
+    }
+}
+
+/// Severity of a log message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    /// Get the name of the level in a [`Palette`], such as `"warn"`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            LogLevel::Trace => "trace",
+            LogLevel::Debug => "debug",
+            LogLevel::Info => "info",
+            LogLevel::Warn => "warn",
+            LogLevel::Error => "error",
+        }
+    }
+}
+
+/// Get the conventional color of a log level: red for errors, yellow for warnings, green for
+/// information, blue for debugging and bright black for tracing. Use
+/// [`Palette::level_color`] to override it.
+pub fn level_color(level: LogLevel) -> Color {
+    match level {
+        LogLevel::Trace => Color::BrightBlack,
+        LogLevel::Debug => Color::Blue,
+        LogLevel::Info => Color::Green,
+        LogLevel::Warn => Color::Yellow,
+        LogLevel::Error => Color::Red,
+    }
+}
+
+/// Get `text` with the foreground color of a log level, see [`level_color`].
+pub fn style_level(level: LogLevel, text: &str) -> ColoredString {
+    level_text(level_color(level), text)
+}
+
+fn level_text(color: Color, text: &str) -> ColoredString {
+    let mut ret = ColoredString::with_capacity(text.len());
+
+    ret.set_fg(&color);
+    ret.push_str(text);
+    ret
+}
+
+/// Get a palette readable on a dark terminal background, defining `"error"`, `"warn"`,
+/// `"info"`, `"success"`, `"debug"`, `"trace"`, `"muted"` and `"highlight"`.
+pub fn theme_dark() -> Palette {
+    Palette::new()
+        .with("error", Color::BrightRed)
+        .with("warn", Color::BrightYellow)
+        .with("info", Color::BrightCyan)
+        .with("success", Color::BrightGreen)
+        .with("debug", Color::BrightBlue)
+        .with("trace", Color::BrightBlack)
+        .with("muted", Color::BrightBlack)
+        .with("highlight", Color::BrightMagenta)
+}
+
+/// Get a palette readable on a light terminal background, defining the same names as
+/// [`theme_dark`].
+pub fn theme_light() -> Palette {
+    Palette::new()
+        .with("error", Color::Red)
+        .with("warn", Color::Yellow)
+        .with("info", Color::Blue)
+        .with("success", Color::Green)
+        .with("debug", Color::Cyan)
+        .with("trace", Color::BrightBlack)
+        .with("muted", Color::BrightBlack)
+        .with("highlight", Color::Magenta)
+}
@@ -0,0 +1,14 @@
+//! Everything needed to build colored strings, to import at once:
+//!
+//! ```
+//! use colost::prelude::*;
+//!
+//! let warning = "x".red().bold();
+//!
+//! assert_eq!(warning.colored(), "\x1b[31;1mx\x1b[0m");
+//! ```
+
+pub use crate::{
+    bar, color_for, colorize_lines, fade, join, join_colored, palette_gradient, strip_ansi, Align,
+    Attribute, Color, ColoredString, Colorize, ResetMode, Style,
+};
@@ -0,0 +1,237 @@
+use crate::sgr::{command_len, sgr_commands, Overrides, SgrState};
+use crate::{ColoredString, ANSI_ESCAPE_END, ANSI_ESCAPE_START};
+
+/// Decimal representation of every code, on three digits.
+static CODE_DIGITS: [u8; 768] = {
+    let mut digits = [0; 768];
+    let mut code = 0;
+
+    while code < 256 {
+        digits[code * 3] = b'0' + (code / 100) as u8;
+        digits[code * 3 + 1] = b'0' + (code / 10 % 10) as u8;
+        digits[code * 3 + 2] = b'0' + (code % 10) as u8;
+        code += 1;
+    }
+
+    digits
+};
+
+/// Get the decimal representation of a code, without allocating.
+fn code_str(code: u8) -> &'static str {
+    let start = code as usize * 3
+        + match code {
+            0..=9 => 2,
+            10..=99 => 1,
+            _ => 0,
+        };
+
+    core::str::from_utf8(&CODE_DIGITS[start..code as usize * 3 + 3]).unwrap_or_default()
+}
+
+/// Iterator over the parts of the colored output of a [`ColoredString`], alternating between
+/// pieces of escape sequences and text, without building the whole output.
+///
+/// Concatenating all the parts gives [`ColoredString::colored`].
+pub struct LazyColored<'a> {
+    cs: &'a ColoredString,
+    /// Index of the marker being rendered.
+    marker: usize,
+    /// Index of the next code of the marker to render.
+    code: usize,
+    /// End of the codes of the marker making the command being emitted.
+    command_end: usize,
+    /// Byte index of the raw text up to which the text has been rendered.
+    index: usize,
+    /// Styling in effect at this stage of the output, to skip the commands without effect.
+    state: SgrState,
+    /// Commands overriding each other among the ones set at the index being rendered.
+    overrides: Overrides,
+    /// Position of the next command among the ones set at the index being rendered.
+    position: usize,
+    /// Whether an escape sequence is opened, waiting for more codes or its end.
+    in_escape: bool,
+    /// Whether the rendering of the end of the string has been queued.
+    done: bool,
+    /// Number of escape sequences opened so far.
+    escapes: usize,
+    /// Start of the escape sequences.
+    introducer: &'static str,
+    pending: [&'a str; 5],
+    pending_len: usize,
+    pending_pos: usize,
+}
+
+impl<'a> LazyColored<'a> {
+    pub(crate) fn new(cs: &'a ColoredString) -> Self {
+        let mut ret = LazyColored {
+            cs,
+            marker: 0,
+            code: 0,
+            command_end: 0,
+            index: 0,
+            state: SgrState::default(),
+            overrides: Overrides::default(),
+            position: 0,
+            in_escape: false,
+            done: false,
+            escapes: 0,
+            introducer: ANSI_ESCAPE_START,
+            pending: [""; 5],
+            pending_len: 0,
+            pending_pos: 0,
+        };
+
+        if cs.raw.is_empty() || cs.code_markers.is_empty() {
+            ret.queue(&[&cs.raw]);
+            ret.done = true;
+        }
+
+        ret
+    }
+
+    fn queue(&mut self, parts: &[&'a str]) {
+        self.pending[..parts.len()].copy_from_slice(parts);
+        self.pending_len = parts.len();
+        self.pending_pos = 0;
+    }
+
+    /// Start the escape sequences with `introducer` instead of `ESC [`.
+    pub(crate) fn with_introducer(mut self, introducer: &'static str) -> Self {
+        self.introducer = introducer;
+        self
+    }
+
+    /// Check whether `part`, yielded by the iterator, is a piece of the text rather than of an
+    /// escape sequence.
+    pub(crate) fn is_text(&self, part: &str) -> bool {
+        self.cs
+            .raw
+            .as_bytes()
+            .as_ptr_range()
+            .contains(&part.as_ptr())
+    }
+
+    /// Get the number of escape sequences opened so far.
+    pub(crate) fn escapes(&self) -> usize {
+        self.escapes
+    }
+
+    /// Get the end of the escape sequence, if one is opened.
+    fn close_escape(&mut self) -> &'a str {
+        if core::mem::take(&mut self.in_escape) {
+            ANSI_ESCAPE_END
+        } else {
+            ""
+        }
+    }
+}
+
+impl<'a> Iterator for LazyColored<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        loop {
+            if self.pending_pos < self.pending_len {
+                let part = self.pending[self.pending_pos];
+
+                self.pending_pos += 1;
+
+                if part.is_empty() {
+                    continue;
+                }
+
+                return Some(part);
+            }
+
+            if self.done {
+                return None;
+            }
+
+            let raw: &'a str = &self.cs.raw;
+
+            // The codes set after all the text style nothing, the final reset would override
+            // them, so they are dropped.
+            let marker = self
+                .cs
+                .code_markers
+                .get(self.marker)
+                .filter(|marker| marker.index < raw.len());
+
+            let Some(marker) = marker else {
+                let end = self.close_escape();
+
+                // No need for a final reset when no text is styled.
+                if self.state.is_default() {
+                    self.queue(&[end, &raw[self.index..]]);
+                } else {
+                    self.escapes += 1;
+                    self.queue(&[
+                        end,
+                        &raw[self.index..],
+                        self.introducer,
+                        "0",
+                        ANSI_ESCAPE_END,
+                    ]);
+                }
+
+                self.done = true;
+                continue;
+            };
+
+            if marker.index > self.index {
+                let end = self.close_escape();
+
+                self.queue(&[end, &raw[self.index..marker.index]]);
+                self.index = marker.index;
+                continue;
+            }
+
+            let codes = &marker.codes;
+            let markers = &self.cs.code_markers;
+
+            // First marker at this index, find the commands overridden by later ones at once.
+            if self.code == 0 && (self.marker == 0 || markers[self.marker - 1].index < marker.index)
+            {
+                self.overrides = Overrides::new(
+                    markers[self.marker..]
+                        .iter()
+                        .take_while(|next| next.index == marker.index)
+                        .flat_map(|next| sgr_commands(&next.codes)),
+                );
+                self.position = 0;
+            }
+
+            if self.code < self.command_end {
+                self.queue(&[";", code_str(codes[self.code])]);
+                self.code += 1;
+            } else if self.code < codes.len() {
+                let command = &codes[self.code..self.code + command_len(&codes[self.code..])];
+                let superseded = self
+                    .state
+                    .is_superseded(command, self.position, &self.overrides);
+
+                self.position += 1;
+
+                if !superseded && self.state.apply(command) {
+                    let start = if self.in_escape {
+                        ";"
+                    } else {
+                        self.escapes += 1;
+                        self.introducer
+                    };
+
+                    self.queue(&[start, code_str(command[0])]);
+                    self.in_escape = true;
+                    self.command_end = self.code + command.len();
+                    self.code += 1;
+                } else {
+                    self.code += command.len();
+                }
+            } else {
+                self.marker += 1;
+                self.code = 0;
+                self.command_end = 0;
+            }
+        }
+    }
+}
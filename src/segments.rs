@@ -0,0 +1,80 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::ColoredString;
+
+/// Iterator over the segments of a [`ColoredString`]: the runs of text along with the SGR
+/// codes in effect on them, as returned by [`ColoredString::segments`].
+pub struct Segments<'a> {
+    cs: &'a ColoredString,
+    /// Index of the next marker to apply.
+    marker: usize,
+    /// Byte index of the raw text up to which the segments have been yielded.
+    index: usize,
+    /// Codes in effect, folded so that the overridden ones are left out.
+    codes: Vec<u8>,
+}
+
+impl<'a> Segments<'a> {
+    pub(crate) fn new(cs: &'a ColoredString) -> Self {
+        Segments {
+            cs,
+            marker: 0,
+            index: 0,
+            codes: Vec::new(),
+        }
+    }
+}
+
+impl<'a> Iterator for Segments<'a> {
+    type Item = (&'a str, Vec<u8>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let markers = &self.cs.code_markers;
+
+        while self.index < self.cs.raw.len() {
+            while let Some(marker) = markers
+                .get(self.marker)
+                .filter(|marker| marker.index <= self.index)
+            {
+                marker.apply(&mut self.codes);
+                self.marker += 1;
+            }
+
+            let end = markers
+                .get(self.marker)
+                .map_or(self.cs.raw.len(), |marker| marker.index);
+            let text = &self.cs.raw[self.index..end];
+
+            self.index = end;
+
+            if !text.is_empty() {
+                return Some((text, self.codes.clone()));
+            }
+        }
+
+        None
+    }
+}
+
+impl<'a> IntoIterator for &'a ColoredString {
+    type Item = (&'a str, Vec<u8>);
+    type IntoIter = Segments<'a>;
+
+    fn into_iter(self) -> Segments<'a> {
+        self.segments()
+    }
+}
+
+impl IntoIterator for ColoredString {
+    type Item = (String, Vec<u8>);
+    type IntoIter = alloc::vec::IntoIter<(String, Vec<u8>)>;
+
+    /// Consume the colored string into its owned segments.
+    fn into_iter(self) -> Self::IntoIter {
+        self.segments()
+            .map(|(text, codes)| (text.to_string(), codes))
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+}
@@ -0,0 +1,318 @@
+use alloc::vec::Vec;
+
+/// Get the length of the SGR command at the beginning of `codes`, the extended color ones
+/// (`38`, `48` and `58`) spanning their arguments.
+pub(crate) fn command_len(codes: &[u8]) -> usize {
+    let len = match codes {
+        [] => 0,
+        [38 | 48 | 58, 5, ..] => 3,
+        [38 | 48 | 58, 2, ..] => 5,
+        _ => 1,
+    };
+
+    len.min(codes.len())
+}
+
+/// Split SGR parameters into commands, see [`command_len`].
+pub(crate) fn sgr_commands(mut codes: &[u8]) -> impl Iterator<Item = &[u8]> {
+    core::iter::from_fn(move || {
+        if codes.is_empty() {
+            return None;
+        }
+
+        let (command, rest) = codes.split_at(command_len(codes));
+
+        codes = rest;
+
+        Some(command)
+    })
+}
+
+/// Fold SGR parameters into the commands still in effect after all of them, in the order they
+/// were set: a command replaces the previous ones changing the same part of the styling, and a
+/// command restoring its default, such as `22`, removes them. The commands unknown to the model
+/// are all kept.
+pub(crate) fn fold_commands(codes: &[u8]) -> Vec<&[u8]> {
+    let mut ret: Vec<&[u8]> = Vec::new();
+
+    for command in sgr_commands(codes) {
+        let code = command[0];
+
+        if code == 0 {
+            ret.clear();
+            continue;
+        }
+
+        let changed = slots(code);
+
+        ret.retain(|other| !slots(other[0]).iter().any(|slot| changed.contains(slot)));
+
+        if changed.is_empty()
+            || changed
+                .iter()
+                .any(|slot| SLOT_DEFAULTS[*slot as usize] != code)
+        {
+            ret.push(command);
+        }
+    }
+
+    ret
+}
+
+/// A SGR command, stored inline.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Command {
+    codes: [u8; 5],
+    len: u8,
+}
+
+impl Command {
+    fn new(codes: &[u8]) -> Self {
+        let mut command = Command {
+            codes: [0; 5],
+            len: codes.len().min(5) as u8,
+        };
+
+        command.codes[..command.len as usize].copy_from_slice(&codes[..command.len as usize]);
+        command
+    }
+
+    fn as_slice(&self) -> &[u8] {
+        &self.codes[..self.len as usize]
+    }
+}
+
+/// Part of the styling of the terminal that a SGR command changes.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Slot {
+    Fg,
+    Bg,
+    UnderlineColor,
+    Bold,
+    Faint,
+    Italic,
+    Underline,
+    Blink,
+    Reverse,
+    Conceal,
+    Strike,
+    Font,
+    Proportional,
+    Frame,
+    Overline,
+}
+
+const SLOT_COUNT: usize = 15;
+
+/// Command restoring the default of each slot.
+const SLOT_DEFAULTS: [u8; SLOT_COUNT] =
+    [39, 49, 59, 22, 22, 23, 24, 25, 27, 28, 29, 10, 50, 54, 55];
+
+/// Order in which [`SgrState::transition`] changes the slots, the attributes first.
+const TRANSITION_ORDER: [Slot; SLOT_COUNT] = [
+    Slot::Bold,
+    Slot::Faint,
+    Slot::Italic,
+    Slot::Underline,
+    Slot::Blink,
+    Slot::Reverse,
+    Slot::Conceal,
+    Slot::Strike,
+    Slot::Font,
+    Slot::Proportional,
+    Slot::Frame,
+    Slot::Overline,
+    Slot::Fg,
+    Slot::Bg,
+    Slot::UnderlineColor,
+];
+
+/// Get the slots changed by the command starting with `code`, the reset excepted.
+fn slots(code: u8) -> &'static [Slot] {
+    match code {
+        30..=39 | 90..=97 => &[Slot::Fg],
+        40..=49 | 100..=107 => &[Slot::Bg],
+        58 | 59 => &[Slot::UnderlineColor],
+        1 => &[Slot::Bold],
+        2 => &[Slot::Faint],
+        22 => &[Slot::Bold, Slot::Faint],
+        3 | 23 => &[Slot::Italic],
+        4 | 21 | 24 => &[Slot::Underline],
+        5 | 6 | 25 => &[Slot::Blink],
+        7 | 27 => &[Slot::Reverse],
+        8 | 28 => &[Slot::Conceal],
+        9 | 29 => &[Slot::Strike],
+        10..=19 => &[Slot::Font],
+        26 | 50 => &[Slot::Proportional],
+        51 | 52 | 54 => &[Slot::Frame],
+        53 | 55 => &[Slot::Overline],
+        _ => &[],
+    }
+}
+
+/// Last position at which each part of the styling is changed among a run of commands, such as
+/// the ones set at the same index, to tell in constant time whether a command of the run is
+/// overridden by a later one.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct Overrides {
+    /// Position, plus one, of the last command changing each slot, 0 when none does.
+    last: [usize; SLOT_COUNT],
+    /// Position, plus one, of the last reset, 0 when there is none.
+    last_reset: usize,
+}
+
+impl Overrides {
+    /// Record the positions of the `commands` of a run, in one pass.
+    pub(crate) fn new<'a>(commands: impl Iterator<Item = &'a [u8]>) -> Self {
+        let mut ret = Overrides::default();
+
+        for (position, command) in commands.enumerate() {
+            match command.first() {
+                Some(0) => ret.last_reset = position + 1,
+                Some(code) => {
+                    for slot in slots(*code) {
+                        ret.last[*slot as usize] = position + 1;
+                    }
+                }
+                None => (),
+            }
+        }
+
+        ret
+    }
+
+    /// Check whether `slot` is changed again after the command at `position`.
+    fn is_overridden(&self, slot: usize, position: usize) -> bool {
+        self.last[slot].max(self.last_reset) > position + 1
+    }
+}
+
+/// Styling state of a terminal, as changed by SGR commands. It starts as the default styling.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct SgrState {
+    slots: [Command; SLOT_COUNT],
+    /// Whether a command unknown to the model has been applied since the last reset.
+    unknown: bool,
+}
+
+impl Default for SgrState {
+    fn default() -> Self {
+        SgrState {
+            slots: SLOT_DEFAULTS.map(|code| Command::new(&[code])),
+            unknown: false,
+        }
+    }
+}
+
+impl SgrState {
+    /// Apply a SGR command to the state, returning whether it changed anything. A command
+    /// unknown to the model is always considered as a change.
+    pub(crate) fn apply(&mut self, command: &[u8]) -> bool {
+        let Some(code) = command.first() else {
+            return false;
+        };
+
+        if *code == 0 {
+            let changed = !self.is_default();
+
+            *self = SgrState::default();
+            return changed;
+        }
+
+        let slots = slots(*code);
+        let new = Command::new(command);
+        let mut changed = slots.is_empty();
+
+        self.unknown |= slots.is_empty();
+
+        for slot in slots {
+            changed |= self.slots[*slot as usize] != new;
+            self.slots[*slot as usize] = new;
+        }
+
+        changed
+    }
+
+    /// Check whether applying `command`, at `position` among the commands set at the same
+    /// index, is useless given the commands applied right after it, before any text: each part
+    /// of the styling it changes must either be already in effect or changed again by a later
+    /// command, as told by `overrides`. This cancels, for instance, a bold disabling
+    /// immediately followed by its enabling.
+    pub(crate) fn is_superseded(
+        &self,
+        command: &[u8],
+        position: usize,
+        overrides: &Overrides,
+    ) -> bool {
+        let Some(code) = command.first() else {
+            return true;
+        };
+
+        if *code == 0 {
+            if self.unknown {
+                return false;
+            }
+
+            return SLOT_DEFAULTS.iter().enumerate().all(|(slot, default)| {
+                self.slots[slot] == Command::new(&[*default])
+                    || overrides.is_overridden(slot, position)
+            });
+        }
+
+        let slots = slots(*code);
+
+        !slots.is_empty()
+            && slots.iter().all(|slot| {
+                self.slots[*slot as usize] == Command::new(command)
+                    || overrides.is_overridden(*slot as usize, position)
+            })
+    }
+
+    /// Get the state after applying all the SGR parameters `codes` to the default styling.
+    pub(crate) fn from_codes(codes: &[u8]) -> Self {
+        let mut ret = SgrState::default();
+
+        for command in sgr_commands(codes) {
+            ret.apply(command);
+        }
+
+        ret
+    }
+
+    /// Get the SGR parameters switching from this state to `to`, changing only the slots which
+    /// differ and turning them off one by one rather than with a full reset. The commands
+    /// unknown to the model are not carried over.
+    pub(crate) fn transition(&self, to: &SgrState) -> Vec<u8> {
+        let is_default = |state: &SgrState, slot: Slot| {
+            state.slots[slot as usize] == Command::new(&[SLOT_DEFAULTS[slot as usize]])
+        };
+        // Bold and faint are both turned off by 22, the one staying on is set again.
+        let intensity_off = [Slot::Bold, Slot::Faint]
+            .into_iter()
+            .any(|slot| !is_default(self, slot) && is_default(to, slot));
+        let mut codes = Vec::new();
+
+        for slot in TRANSITION_ORDER {
+            let target = to.slots[slot as usize];
+
+            if intensity_off && matches!(slot, Slot::Bold | Slot::Faint) {
+                if slot == Slot::Bold {
+                    codes.push(22);
+                }
+
+                if !is_default(to, slot) {
+                    codes.extend_from_slice(target.as_slice());
+                }
+            } else if self.slots[slot as usize] != target {
+                codes.extend_from_slice(target.as_slice());
+            }
+        }
+
+        codes
+    }
+
+    /// Check whether the state is the default styling.
+    pub(crate) fn is_default(&self) -> bool {
+        *self == SgrState::default()
+    }
+}
@@ -0,0 +1,267 @@
+use alloc::vec::Vec;
+
+use crate::sgr::{sgr_commands, SgrState};
+use crate::{Color, ColoredString};
+
+/// Set of colors and attributes applied together to a piece of text. The default style has no
+/// color nor attribute.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Style {
+    pub fg: Option<Color>,
+    pub bg: Option<Color>,
+    pub bold: bool,
+    pub faint: bool,
+    pub italic: bool,
+    pub underline: bool,
+    pub blink: bool,
+    pub reverse: bool,
+    pub strike: bool,
+}
+
+/// Styling in effect at a stage of a colored string, as captured by
+/// [`ColoredString::save_style`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StyleSnapshot {
+    codes: Vec<u8>,
+}
+
+/// Color in effect, as set by any kind of color code.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ActiveColor {
+    /// One of the 16 basic colors.
+    Basic(Color),
+    /// Index in the 256 colors palette.
+    Palette(u8),
+    /// 24-bit RGB color.
+    Rgb(u8, u8, u8),
+}
+
+/// Colors and attributes in effect at a position of a colored string, as reported by
+/// [`ColoredString::char_colors`]. Unlike [`Style`], it holds any kind of color and tells the
+/// blink speeds apart. The codes it does not model, such as the fonts or the underline color,
+/// are ignored.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ActiveStyle {
+    pub fg: Option<ActiveColor>,
+    pub bg: Option<ActiveColor>,
+    pub bold: bool,
+    pub faint: bool,
+    pub italic: bool,
+    pub underline: bool,
+    pub slow_blink: bool,
+    pub fast_blink: bool,
+    pub reverse: bool,
+    pub conceal: bool,
+    pub strike: bool,
+}
+
+impl Style {
+    /// Create a style without color nor attribute.
+    pub fn new() -> Self {
+        Style::default()
+    }
+
+    /// Set the foreground color, builder style.
+    pub fn fg(mut self, color: Color) -> Self {
+        self.fg = Some(color);
+        self
+    }
+
+    /// Set the background color, builder style.
+    pub fn bg(mut self, color: Color) -> Self {
+        self.bg = Some(color);
+        self
+    }
+
+    /// Enable the bold attribute, builder style.
+    pub fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+
+    /// Enable the faint attribute, builder style.
+    pub fn faint(mut self) -> Self {
+        self.faint = true;
+        self
+    }
+
+    /// Enable the italic attribute, builder style.
+    pub fn italic(mut self) -> Self {
+        self.italic = true;
+        self
+    }
+
+    /// Enable the underline attribute, builder style.
+    pub fn underline(mut self) -> Self {
+        self.underline = true;
+        self
+    }
+
+    /// Enable the slow blinking, builder style.
+    pub fn blink(mut self) -> Self {
+        self.blink = true;
+        self
+    }
+
+    /// Enable the swap of the foreground and background colors, builder style.
+    pub fn reverse(mut self) -> Self {
+        self.reverse = true;
+        self
+    }
+
+    /// Enable the strikethrough attribute, builder style.
+    pub fn strike(mut self) -> Self {
+        self.strike = true;
+        self
+    }
+
+    /// Get the SGR parameters setting the style over the default one, the attributes first.
+    pub fn codes(&self) -> Vec<u8> {
+        let attributes = [
+            (self.bold, 1),
+            (self.faint, 2),
+            (self.italic, 3),
+            (self.underline, 4),
+            (self.blink, 5),
+            (self.reverse, 7),
+            (self.strike, 9),
+        ];
+        let mut codes: Vec<u8> = attributes
+            .into_iter()
+            .filter(|(enabled, _)| *enabled)
+            .map(|(_, code)| code)
+            .collect();
+
+        codes.extend(self.fg.map(|color| 30 + color.int_value()));
+        codes.extend(self.bg.map(|color| 40 + color.int_value()));
+        codes
+    }
+}
+
+/// Get the SGR parameters switching from the `from` style to the `to` one, with only the
+/// changed parts: the disabled attributes and removed colors are turned off one by one rather
+/// than with a full reset. The styles are compared with the model the rendering uses to skip
+/// the codes without effect.
+pub fn transition_codes(from: &Style, to: &Style) -> Vec<u8> {
+    SgrState::from_codes(&from.codes()).transition(&SgrState::from_codes(&to.codes()))
+}
+
+impl ActiveStyle {
+    /// Update the style with the SGR parameters `codes`.
+    fn apply(&mut self, codes: &[u8]) {
+        for command in sgr_commands(codes) {
+            match *command {
+                [0] => *self = ActiveStyle::default(),
+                [1] => self.bold = true,
+                [2] => self.faint = true,
+                [3] => self.italic = true,
+                [4] => self.underline = true,
+                [5] => self.slow_blink = true,
+                [6] => self.fast_blink = true,
+                [7] => self.reverse = true,
+                [8] => self.conceal = true,
+                [9] => self.strike = true,
+                [22] => (self.bold, self.faint) = (false, false),
+                [23] => self.italic = false,
+                [24] => self.underline = false,
+                [25] => (self.slow_blink, self.fast_blink) = (false, false),
+                [27] => self.reverse = false,
+                [28] => self.conceal = false,
+                [29] => self.strike = false,
+                [39] => self.fg = None,
+                [49] => self.bg = None,
+                [code @ (30..=37 | 90..=97)] => {
+                    self.fg = Color::from_code(code).map(ActiveColor::Basic)
+                }
+                [code @ (40..=47 | 100..=107)] => {
+                    self.bg = Color::from_code(code).map(ActiveColor::Basic)
+                }
+                [38, 5, index] => self.fg = Some(ActiveColor::Palette(index)),
+                [48, 5, index] => self.bg = Some(ActiveColor::Palette(index)),
+                [38, 2, r, g, b] => self.fg = Some(ActiveColor::Rgb(r, g, b)),
+                [48, 2, r, g, b] => self.bg = Some(ActiveColor::Rgb(r, g, b)),
+                _ => (),
+            }
+        }
+    }
+}
+
+impl ColoredString {
+    /// Get each character of the raw content with the colors and attributes in effect at its
+    /// position, for instance to render the string with another medium than a terminal.
+    pub fn char_colors(&self) -> Vec<(char, ActiveStyle)> {
+        let mut ret = Vec::with_capacity(self.raw.len());
+        let mut style = ActiveStyle::default();
+        let mut markers = self.code_markers.iter().peekable();
+
+        for (index, ch) in self.raw.char_indices() {
+            while let Some(marker) = markers.next_if(|marker| marker.index <= index) {
+                style.apply(&marker.codes);
+            }
+
+            ret.push((ch, style));
+        }
+
+        ret
+    }
+
+    /// Apply the colors and attributes of `style` from this stage of the string, on top of the
+    /// styling already in effect.
+    pub fn set_style(&mut self, style: &Style) {
+        self.push_codes(&style.codes());
+    }
+
+    /// Build a colored string from pieces of text each with its own style, the styling being
+    /// reset between the pieces.
+    pub fn from_styled_spans(spans: &[(&str, Style)]) -> ColoredString {
+        let mut ret = ColoredString::with_capacity(spans.iter().map(|(text, _)| text.len()).sum());
+
+        for (text, style) in spans {
+            if !ret.active_codes_at(usize::MAX).is_empty() {
+                ret.reset();
+            }
+
+            ret.set_style(style);
+            ret.push_str(text);
+        }
+
+        ret
+    }
+
+    /// Capture the colors and attributes in effect at the end of the string, to set them
+    /// again later with [`restore_style`](Self::restore_style).
+    pub fn save_style(&self) -> StyleSnapshot {
+        StyleSnapshot {
+            codes: self.active_codes_at(usize::MAX),
+        }
+    }
+
+    /// Set the styling captured by `snapshot` from this stage of the string, replacing the one
+    /// in effect.
+    pub fn restore_style(&mut self, snapshot: &StyleSnapshot) {
+        if self.active_codes_at(usize::MAX) == snapshot.codes {
+            return;
+        }
+
+        let mut codes = Vec::with_capacity(snapshot.codes.len() + 1);
+
+        codes.push(0);
+        codes.extend_from_slice(&snapshot.codes);
+        self.push_codes(&codes);
+    }
+
+    /// Apply `style` on top of the styling in effect, until the matching
+    /// [`pop_style`](Self::pop_style). The scopes nest.
+    pub fn push_style(&mut self, style: &Style) {
+        self.style_stack.push(self.save_style());
+        self.set_style(style);
+    }
+
+    /// Restore the styling in effect before the last [`push_style`](Self::push_style). Does
+    /// nothing when no style scope is opened.
+    pub fn pop_style(&mut self) {
+        if let Some(snapshot) = self.style_stack.pop() {
+            self.restore_style(&snapshot);
+        }
+    }
+}
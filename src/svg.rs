@@ -0,0 +1,137 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use crate::level::palette_rgb;
+use crate::{ActiveColor, ActiveStyle, Color, ColoredString};
+
+/// Size of the font, in pixels.
+const FONT_SIZE: usize = 15;
+/// Width of a character, in pixels, for a monospace font of [`FONT_SIZE`].
+const CELL_WIDTH: usize = 9;
+/// Height of a line, in pixels.
+const LINE_HEIGHT: usize = 18;
+
+/// Get the `#rrggbb` notation of a color, the basic ones and the ones of the 256 colors palette
+/// taking their value in the default xterm palette unless overridden by
+/// [`set_rgb_palette`](crate::set_rgb_palette).
+fn hex(color: ActiveColor) -> String {
+    let (r, g, b) = match color {
+        ActiveColor::Basic(color) => palette_rgb(color as u8),
+        ActiveColor::Palette(index) => palette_rgb(index),
+        ActiveColor::Rgb(r, g, b) => (r, g, b),
+    };
+
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+/// Push `text` to `out`, escaping the characters with a meaning in XML.
+fn push_escaped(out: &mut String, text: &str) {
+    for ch in text.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(ch),
+        }
+    }
+}
+
+/// Push the attributes of a `<tspan>` with `style` to `out`.
+fn push_attributes(out: &mut String, style: &ActiveStyle) {
+    if let Some(fg) = style.fg {
+        let _ = write!(out, " fill=\"{}\"", hex(fg));
+    }
+
+    if style.bold {
+        out.push_str(" font-weight=\"bold\"");
+    }
+
+    if style.italic {
+        out.push_str(" font-style=\"italic\"");
+    }
+
+    if style.underline {
+        out.push_str(" text-decoration=\"underline\"");
+    }
+}
+
+impl ColoredString {
+    /// Render the string as a SVG image, such as to show the output of a program in a
+    /// documentation: one `<text>` per line with a `<tspan>` per styled run, in a monospace
+    /// font, over a black background. The colors take their value in the default xterm
+    /// palette, see [`Color::rgb`], or in the one set by
+    /// [`set_rgb_palette`](crate::set_rgb_palette). The foreground and background colors, bold,
+    /// italic and underline are rendered, the other attributes are ignored. Each character
+    /// takes one column.
+    pub fn to_svg(&self) -> String {
+        let chars = self.char_colors();
+        let lines: Vec<&[(char, ActiveStyle)]> = chars.split(|(ch, _)| *ch == '\n').collect();
+        let columns = lines.iter().map(|line| line.len()).max().unwrap_or(0);
+        let mut backgrounds = String::new();
+        let mut texts = String::new();
+
+        for (row, line) in lines.iter().enumerate() {
+            let _ = write!(
+                texts,
+                "<text x=\"0\" y=\"{}\" xml:space=\"preserve\">",
+                row * LINE_HEIGHT + FONT_SIZE - 1
+            );
+
+            for (column, run) in line
+                .chunk_by(|(_, a), (_, b)| a == b)
+                .scan(0, |column, run| {
+                    let start = *column;
+
+                    *column += run.len();
+                    Some((start, run))
+                })
+            {
+                let style = run[0].1;
+                let text: String = run.iter().map(|(ch, _)| *ch).collect();
+
+                if let Some(bg) = style.bg {
+                    let _ = write!(
+                        backgrounds,
+                        "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+                        column * CELL_WIDTH,
+                        row * LINE_HEIGHT,
+                        run.len() * CELL_WIDTH,
+                        LINE_HEIGHT,
+                        hex(bg)
+                    );
+                }
+
+                if style.fg.is_none() && !style.bold && !style.italic && !style.underline {
+                    push_escaped(&mut texts, &text);
+                } else {
+                    texts.push_str("<tspan");
+                    push_attributes(&mut texts, &style);
+                    texts.push('>');
+                    push_escaped(&mut texts, &text);
+                    texts.push_str("</tspan>");
+                }
+            }
+
+            texts.push_str("</text>");
+        }
+
+        let mut ret = String::with_capacity(backgrounds.len() + texts.len() + 256);
+        let _ = write!(
+            ret,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" \
+             font-family=\"monospace\" font-size=\"{}\" fill=\"{}\">\
+             <rect width=\"100%\" height=\"100%\" fill=\"#000000\"/>",
+            columns * CELL_WIDTH,
+            lines.len() * LINE_HEIGHT,
+            FONT_SIZE,
+            hex(ActiveColor::Basic(Color::White))
+        );
+        ret.push_str(&backgrounds);
+        ret.push_str(&texts);
+        ret.push_str("</svg>");
+        ret
+    }
+}
@@ -0,0 +1,137 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::ColoredString;
+
+/// Table of colored cells, rendered with its columns aligned on the visible width of the
+/// cells, whatever their styling.
+#[derive(Clone, Debug, Default)]
+pub struct Table {
+    rows: Vec<Vec<ColoredString>>,
+    border: bool,
+}
+
+impl Table {
+    /// Create an empty table, without border.
+    pub fn new() -> Self {
+        Table::default()
+    }
+
+    /// Draw a border around the table and between its columns with Unicode box-drawing
+    /// characters, builder style.
+    pub fn with_border(mut self, border: bool) -> Self {
+        self.border = border;
+        self
+    }
+
+    /// Add a row of cells, builder style.
+    pub fn with_row(mut self, row: Vec<ColoredString>) -> Self {
+        self.add_row(row);
+        self
+    }
+
+    /// Add a row of cells. The rows may have different numbers of cells, the missing ones
+    /// being empty.
+    pub fn add_row(&mut self, row: Vec<ColoredString>) {
+        self.rows.push(row);
+    }
+
+    /// Split a cell into its lines, each one with the styling in effect on it.
+    fn cell_lines(cell: &ColoredString) -> Vec<ColoredString> {
+        let raw = cell.raw_str();
+        let mut lines = Vec::new();
+        let mut start = 0;
+
+        for (index, _) in raw.match_indices('\n') {
+            lines.push(cell.slice(start, index));
+            start = index + 1;
+        }
+
+        lines.push(cell.slice(start, raw.len()));
+        lines
+    }
+
+    /// Get the width of each column, as the widest line of its cells.
+    fn column_widths(rows: &[Vec<Vec<ColoredString>>]) -> Vec<usize> {
+        let mut widths = Vec::new();
+
+        for row in rows.iter() {
+            widths.resize(widths.len().max(row.len()), 0);
+
+            for (width, lines) in widths.iter_mut().zip(row.iter()) {
+                for line in lines.iter() {
+                    *width = (*width).max(line.width());
+                }
+            }
+        }
+
+        widths
+    }
+
+    /// Render the table, one line per row. Without border, the columns are separated by two
+    /// spaces and the last one is not padded. A cell holding several lines makes its row as
+    /// tall as them, the other cells of the row being padded with empty lines.
+    pub fn render(&self) -> ColoredString {
+        let rows: Vec<Vec<Vec<ColoredString>>> = self
+            .rows
+            .iter()
+            .map(|row| row.iter().map(Table::cell_lines).collect())
+            .collect();
+        let widths = Table::column_widths(&rows);
+        let empty = ColoredString::new();
+        let mut ret = ColoredString::new();
+        let rule = |left: &str, middle: &str, right: &str| {
+            let columns: Vec<String> = widths.iter().map(|width| "─".repeat(width + 2)).collect();
+
+            format!("{}{}{}", left, columns.join(middle), right)
+        };
+
+        if self.border {
+            ret.push_str(&rule("┌", "┬", "┐"));
+            ret.push('\n');
+        }
+
+        for (i, row) in rows.iter().enumerate() {
+            let height = row.iter().map(Vec::len).max().unwrap_or(1);
+
+            for line in 0..height {
+                if i > 0 || line > 0 {
+                    ret.push('\n');
+                }
+
+                if self.border {
+                    ret.push_str("│ ");
+                }
+
+                for (column, width) in widths.iter().enumerate() {
+                    let cell = row
+                        .get(column)
+                        .and_then(|lines| lines.get(line))
+                        .unwrap_or(&empty);
+
+                    if column > 0 {
+                        ret.push_str(if self.border { " │ " } else { "  " });
+                    }
+
+                    if self.border || column + 1 < widths.len() {
+                        ret.push_colored(&cell.ljust(*width, ' '));
+                    } else {
+                        ret.push_colored(cell);
+                    }
+                }
+
+                if self.border {
+                    ret.push_str(" │");
+                }
+            }
+        }
+
+        if self.border {
+            ret.push('\n');
+            ret.push_str(&rule("└", "┴", "┘"));
+        }
+
+        ret
+    }
+}
@@ -0,0 +1,69 @@
+use alloc::vec::Vec;
+
+use crate::ColoredString;
+
+/// Suspicious pattern of the codes of a colored string, reported by
+/// [`ColoredString::validate`]. The indexes are byte indexes of the raw text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StyleWarning {
+    /// A marker placed before the previous one.
+    UnorderedMarker { index: usize },
+    /// A marker without any code.
+    EmptyMarker { index: usize },
+    /// A marker past the end of the raw text or within a character.
+    MarkerOutsideText { index: usize },
+    /// A reset of the styling in the middle of a word, leaving its end uncolored. A reset
+    /// between words is not reported, being how a highlight normally ends.
+    ResetMidWord { index: usize },
+}
+
+impl ColoredString {
+    /// Check the codes of the string for suspicious patterns, which are most likely logic
+    /// errors of the code building the string: markers out of order, empty or outside of the
+    /// text, and a [`reset`](Self::reset) in the middle of a word. A reset followed by uncolored
+    /// text at a word boundary, such as after `"err"` in `"err: details"`, cannot be told apart
+    /// from the end of a highlight and is not reported. It is meant for debug builds, for
+    /// instance as `debug_assert!(cs.validate().is_ok())`.
+    ///
+    /// It is not run by the building methods themselves: a reset in the middle of a word is
+    /// legitimate when highlighting a part of it, and the check walks all the codes, which
+    /// would make each push quadratic in debug builds.
+    pub fn validate(&self) -> Result<(), Vec<StyleWarning>> {
+        let mut warnings = Vec::new();
+        let mut active = Vec::new();
+        let mut last_index = 0;
+
+        for marker in self.code_markers.iter() {
+            let index = marker.index;
+
+            if index < last_index {
+                warnings.push(StyleWarning::UnorderedMarker { index });
+            }
+
+            if marker.codes.is_empty() {
+                warnings.push(StyleWarning::EmptyMarker { index });
+            }
+
+            if !self.raw.is_char_boundary(index) {
+                warnings.push(StyleWarning::MarkerOutsideText { index });
+            } else if marker.codes == [0] && !active.is_empty() {
+                let is_word = |ch: Option<char>| ch.is_some_and(char::is_alphanumeric);
+
+                if is_word(self.raw[..index].chars().next_back())
+                    && is_word(self.raw[index..].chars().next())
+                {
+                    warnings.push(StyleWarning::ResetMidWord { index });
+                }
+            }
+
+            marker.apply(&mut active);
+            last_index = last_index.max(index);
+        }
+
+        if warnings.is_empty() {
+            Ok(())
+        } else {
+            Err(warnings)
+        }
+    }
+}
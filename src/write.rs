@@ -0,0 +1,51 @@
+use std::fs::File;
+use std::io::{self, BufRead, BufWriter, Write};
+use std::path::Path;
+
+use crate::ColoredString;
+
+impl ColoredString {
+    /// Write the colored string to `writer`, part by part without building the whole output.
+    /// The short writes and the interruptions, common with pipes and sockets, are retried until
+    /// each part is written. On any other error, the writing stops and the error is returned:
+    /// the output is then cut, possibly within an escape sequence, but the string is left
+    /// untouched and can be written again.
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        for part in self.render_parts() {
+            writer.write_all(part.as_bytes())?;
+        }
+
+        Ok(())
+    }
+
+    /// Write the string to the file at `path`, creating or truncating it. The content is
+    /// deliberately written without colors, as files rarely want escape sequences.
+    pub fn write_to_file(&self, path: &Path) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+
+        writer.write_all(self.raw.as_bytes())?;
+        writer.flush()
+    }
+}
+
+/// Color the lines read from `reader` with `rule` and write them to `writer` as they come, the
+/// whole input never being loaded. The line endings are kept as is. The writer is flushed after
+/// each line, so the output follows a continuous input such as the one of `tail -f`.
+pub fn colorize_stream<R: BufRead, W: Write>(
+    mut reader: R,
+    mut writer: W,
+    mut rule: impl FnMut(&str) -> ColoredString,
+) -> io::Result<()> {
+    let mut line = String::new();
+
+    while reader.read_line(&mut line)? > 0 {
+        let content = line.trim_end_matches(['\n', '\r']);
+
+        rule(content).write_to(&mut writer)?;
+        writer.write_all(&line.as_bytes()[content.len()..])?;
+        writer.flush()?;
+        line.clear();
+    }
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use colost::Color;
+use colost::ColoredString;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt::Write;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Allocator counting the allocations, to check the rendering does not allocate.
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+/// Sink discarding what is written to it.
+struct Discard;
+
+impl Write for Discard {
+    fn write_str(&mut self, _: &str) -> std::fmt::Result {
+        Ok(())
+    }
+}
+
+#[test]
+fn rendering_does_not_allocate() {
+    let mut cs = ColoredString::new();
+
+    for i in 0..1000 {
+        cs.set_fg(&Color::ALL[i % 16]);
+        cs.set_bold(i % 3 == 0);
+        cs.push_str("x");
+    }
+
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+
+    assert!(cs.render_parts().count() > 1000);
+    write!(Discard, "{}", cs).unwrap();
+
+    assert_eq!(ALLOCATIONS.load(Ordering::Relaxed), before);
+}
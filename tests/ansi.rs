@@ -0,0 +1,115 @@
+#[test]
+fn strip_ansi_removes_all_escapes() {
+    let s = "\x1b]0;title\x07\x1b[2J\x1b[31mred\x1b[0m \x1b[1;1Hplain\x1b(B";
+
+    assert_eq!(colost::strip_ansi(s), "red plain");
+}
+
+#[test]
+fn strip_sgr_keeps_other_escapes() {
+    let s = "\x1b[31;1mred\x1b[0m\x1b[2Anext";
+
+    assert_eq!(colost::strip_sgr(s), "red\x1b[2Anext");
+    assert_eq!(colost::strip_ansi(s), "rednext");
+}
+
+#[test]
+fn parse_events() {
+    use colost::AnsiEvent;
+
+    let events: Vec<AnsiEvent> =
+        colost::AnsiParser::new("a\x1b[31;1mred\x1b[m\x1b[2Ab\x1b[;38:5:300m").collect();
+
+    assert_eq!(
+        events,
+        [
+            AnsiEvent::Text("a"),
+            AnsiEvent::Sgr(vec![31, 1]),
+            AnsiEvent::Text("red"),
+            AnsiEvent::Sgr(vec![0]),
+            AnsiEvent::Escape("\x1b[2A"),
+            AnsiEvent::Text("b"),
+            AnsiEvent::Sgr(vec![0, 38, 5, 255]),
+        ]
+    );
+}
+
+#[test]
+fn parse_sub_params() {
+    use colost::AnsiEvent;
+
+    let events: Vec<AnsiEvent> = colost::AnsiParser::new(
+        "\x1b[38:2::255:0:0m\x1b[48:2:1:2:3m\x1b[1;38:5:21m\x1b[4:3m\x1b[4:0m",
+    )
+    .collect();
+
+    assert_eq!(
+        events,
+        [
+            AnsiEvent::Sgr(vec![38, 2, 255, 0, 0]),
+            AnsiEvent::Sgr(vec![48, 2, 1, 2, 3]),
+            AnsiEvent::Sgr(vec![1, 38, 5, 21]),
+            AnsiEvent::Sgr(vec![4]),
+            AnsiEvent::Sgr(vec![24]),
+        ]
+    );
+}
+
+#[test]
+fn parse_ansi_round_trips_modeled_codes() {
+    use colost::{ColorLevel, ColoredString};
+
+    let s = "\x1b[1;31mbold red\x1b[0m plain \x1b[44mbg\x1b[0m \x1b[38;2;1;2;3mrgb\x1b[0m";
+    let cs = ColoredString::parse_ansi(s);
+
+    assert_eq!(cs.raw(), "bold red plain bg rgb");
+    assert_eq!(cs.render_for(ColorLevel::TrueColor), s);
+    assert_eq!(cs.render_for(ColorLevel::NoColor), "bold red plain bg rgb");
+
+    // The sub-parameters forms are rendered with `;` separators.
+    let cs = ColoredString::parse_ansi("\x1b[38:2::255:0:0mred\x1b[0m \x1b[4:3mcurly\x1b[4:0m!");
+
+    assert_eq!(
+        cs.render_for(ColorLevel::TrueColor),
+        "\x1b[38;2;255;0;0mred\x1b[0m \x1b[4mcurly\x1b[24m!"
+    );
+}
+
+#[test]
+fn parse_ansi_drops_other_escapes() {
+    let cs = colost::ColoredString::parse_ansi("\x1b[2J\x1b[32mok\x1b[0m");
+
+    assert_eq!(cs.colored(), "\x1b[32mok\x1b[0m");
+}
+
+#[test]
+fn render_for_downgrades_colors() {
+    use colost::{ColorLevel, ColoredString};
+
+    let cs = ColoredString::parse_ansi("\x1b[38;2;255;0;0mred\x1b[0m \x1b[48;5;21mblue\x1b[0m");
+
+    assert_eq!(
+        cs.render_for(ColorLevel::Ansi256),
+        "\x1b[38;5;196mred\x1b[0m \x1b[48;5;21mblue\x1b[0m"
+    );
+    assert_eq!(
+        cs.render_for(ColorLevel::Ansi16),
+        "\x1b[91mred\x1b[0m \x1b[44mblue\x1b[0m"
+    );
+}
+
+#[test]
+fn push_ansi_str_turns_escapes_into_codes() {
+    use colost::{Color, ColoredString};
+
+    let mut cs = ColoredString::new();
+
+    cs.push_str("a ");
+    cs.push_ansi_str("\x1b[31mred\x1b[0m");
+    cs.push_str(" ");
+    cs.set_fg(&Color::Blue);
+    cs.push_str("b");
+
+    assert_eq!(cs.raw(), "a red b");
+    assert_eq!(cs.colored(), "a \x1b[31mred\x1b[0m \x1b[34mb\x1b[0m");
+}
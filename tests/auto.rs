@@ -0,0 +1,96 @@
+#![cfg(feature = "std")]
+
+use colost::Color;
+use colost::ColoredString;
+use std::sync::Mutex;
+
+/// The tests change the global state, they must not run concurrently.
+static LOCK: Mutex<()> = Mutex::new(());
+
+fn red(text: &str) -> ColoredString {
+    let mut cs = ColoredString::new();
+
+    cs.set_fg(&Color::Red);
+    cs.push_str(text);
+
+    cs
+}
+
+#[test]
+#[cfg(not(target_arch = "wasm32"))]
+fn dumb_terminal_gets_plain_output() {
+    let _lock = LOCK.lock().unwrap();
+    let term = std::env::var_os("TERM");
+
+    std::env::set_var("TERM", "dumb");
+
+    assert!(!colost::should_colorize());
+    assert_eq!(red("hi").colored_auto(), "hi");
+
+    match term {
+        Some(term) => std::env::set_var("TERM", term),
+        None => std::env::remove_var("TERM"),
+    }
+}
+
+#[test]
+fn color_support_rules() {
+    use std::ffi::OsStr;
+
+    let xterm = Some(OsStr::new("xterm"));
+
+    assert!(colost::supports_color(xterm, None, true));
+    assert!(colost::supports_color(xterm, Some(OsStr::new("")), true));
+    assert!(!colost::supports_color(xterm, None, false));
+    assert!(!colost::supports_color(xterm, Some(OsStr::new("1")), true));
+    assert!(!colost::supports_color(
+        Some(OsStr::new("dumb")),
+        None,
+        true
+    ));
+    assert!(!colost::supports_color(None, None, true));
+}
+
+#[test]
+#[cfg(not(target_arch = "wasm32"))]
+fn forced_color_wins_over_detection() {
+    let _lock = LOCK.lock().unwrap();
+    let term = std::env::var_os("TERM");
+
+    std::env::set_var("NO_COLOR", "1");
+    colost::force_color(true);
+
+    assert_eq!(red("hi").colored_auto(), "\x1b[31mhi\x1b[0m");
+
+    colost::force_color(false);
+    std::env::remove_var("NO_COLOR");
+    std::env::set_var("TERM", "xterm");
+
+    assert_eq!(red("hi").colored_auto(), "hi");
+
+    colost::clear_force_color();
+    std::env::set_var("NO_COLOR", "1");
+
+    assert_eq!(red("hi").colored_auto(), "hi");
+
+    std::env::remove_var("NO_COLOR");
+
+    match term {
+        Some(term) => std::env::set_var("TERM", term),
+        None => std::env::remove_var("TERM"),
+    }
+}
+
+#[test]
+#[cfg(target_arch = "wasm32")]
+fn wasm_is_colored_unless_forced_off() {
+    let _lock = LOCK.lock().unwrap();
+
+    assert_eq!(red("hi").colored_auto(), "\x1b[31mhi\x1b[0m");
+
+    colost::force_color(false);
+
+    assert_eq!(red("hi").colored_auto(), "hi");
+
+    colost::clear_force_color();
+}
@@ -0,0 +1,52 @@
+use colost::Color;
+use colost::ColoredString;
+use colost::DecodeError;
+
+#[test]
+fn bytes_round_trip() {
+    let mut cs = ColoredString::new();
+
+    cs.push_str("plain ");
+    cs.set_color(&Color::Red, &Color::Blue);
+    cs.push_str(&"é".repeat(100));
+    cs.set_fg_rgb(1, 2, 3);
+    cs.set_fg(&Color::Green);
+    cs.reset();
+    cs.push_str("!");
+
+    let bytes = cs.to_bytes();
+
+    assert_eq!(ColoredString::from_bytes(&bytes), Ok(cs));
+    assert_eq!(
+        ColoredString::from_bytes(&ColoredString::new().to_bytes()),
+        Ok(ColoredString::new())
+    );
+}
+
+#[test]
+fn from_bytes_rejects_invalid_data() {
+    let mut cs = ColoredString::new();
+
+    cs.set_fg(&Color::Red);
+    cs.push_str("é");
+
+    let bytes = cs.to_bytes();
+
+    assert_eq!(bytes, [1, 2, 0xc3, 0xa9, 1, 0, 1, 31]);
+    assert_eq!(
+        ColoredString::from_bytes(&bytes[..bytes.len() - 1]),
+        Err(DecodeError::Truncated)
+    );
+    assert_eq!(
+        ColoredString::from_bytes(&[2]),
+        Err(DecodeError::UnsupportedVersion(2))
+    );
+    assert_eq!(
+        ColoredString::from_bytes(&[1, 1, 0xff, 0]),
+        Err(DecodeError::InvalidUtf8)
+    );
+    assert_eq!(
+        ColoredString::from_bytes(&[1, 2, 0xc3, 0xa9, 1, 1, 1, 31]),
+        Err(DecodeError::InvalidMarker)
+    );
+}
@@ -0,0 +1,96 @@
+use colost::Color;
+use colost::ColoredString;
+use colost::StyleError;
+
+#[test]
+fn nearest_ansi16_of_palette_values() {
+    for color in Color::ALL {
+        assert_eq!(Color::nearest_ansi16(color.rgb()), color);
+    }
+
+    assert_eq!(Color::nearest_ansi16((250, 10, 5)), Color::BrightRed);
+}
+
+#[test]
+fn complement_colors() {
+    assert_eq!(Color::Red.complement(), Color::BrightCyan);
+    assert_eq!(Color::Blue.complement(), Color::BrightYellow);
+    assert_eq!(Color::Black.complement(), Color::BrightWhite);
+    assert_eq!(Color::BrightWhite.complement(), Color::Black);
+}
+
+#[test]
+fn cycle_colors() {
+    assert_eq!(Color::Red.next(), Color::Green);
+    assert_eq!(Color::Red.prev(), Color::Black);
+    assert_eq!(Color::BrightWhite.next(), Color::Black);
+    assert_eq!(Color::Black.prev(), Color::BrightWhite);
+
+    for color in Color::ALL {
+        assert_eq!(color.next().prev(), color);
+    }
+}
+
+#[test]
+fn color_for_is_deterministic() {
+    assert_eq!(colost::color_for("alice"), colost::color_for("alice"));
+
+    let colors: Vec<Color> = ["alice", "bob", "carol", "dave", "eve"]
+        .iter()
+        .map(|key| colost::color_for(key))
+        .collect();
+
+    assert!(colors.iter().any(|color| *color != colors[0]));
+}
+
+#[test]
+fn color_for_excluding_colors() {
+    let excluded = [
+        Color::Black,
+        Color::BrightBlack,
+        Color::White,
+        Color::BrightWhite,
+    ];
+
+    for key in ["a", "b", "c", "d", "e", "f", "g", "h"] {
+        assert!(!excluded.contains(&colost::color_for_excluding(key, &excluded)));
+    }
+
+    assert_eq!(
+        colost::color_for_excluding("a", &Color::ALL),
+        colost::color_for("a")
+    );
+}
+
+#[test]
+fn cube_and_gray_check_their_range() {
+    assert_eq!(colost::cube(0, 0, 0), Ok(16));
+    assert_eq!(colost::cube(5, 2, 1), Ok(209));
+    assert_eq!(
+        colost::cube(6, 0, 0),
+        Err(StyleError::ComponentOutOfRange { value: 6, max: 5 })
+    );
+    assert_eq!(colost::gray(23), Ok(255));
+    assert!(colost::gray(24).is_err());
+}
+
+#[test]
+fn checked_cube_setters() {
+    let mut cs = ColoredString::new();
+
+    assert!(cs.try_set_fg_cube(6, 0, 0).is_err());
+    assert_eq!(cs.try_set_bg_cube(0, 0, 5), Ok(()));
+    cs.set_fg_256(colost::gray(0).unwrap());
+    cs.push_str("a");
+
+    assert_eq!(cs.colored(), "\x1b[48;5;21;38;5;232ma\x1b[0m");
+}
+
+#[test]
+fn color_codes_match_int_value() {
+    for (i, color) in Color::ALL.iter().enumerate() {
+        assert_eq!(colost::COLOR_CODES[i], color.int_value());
+    }
+
+    assert_eq!(colost::COLOR_CODES[Color::BrightRed as usize], 61);
+}
@@ -0,0 +1,1225 @@
+use colost::Attribute;
+use colost::Color;
+use colost::ColoredString;
+use colost::ResetMode;
+use colost::StyleWarning;
+
+#[test]
+fn debug_shows_text_and_codes() {
+    let mut cs = ColoredString::new();
+
+    cs.set_fg(&Color::Red);
+    cs.push_str("hello");
+    cs.reset();
+
+    let debug = format!("{:?}", cs);
+
+    assert!(debug.contains("\"hello\""));
+    assert!(debug.contains("(0, 31)"));
+    assert!(debug.contains("(5, 0)"));
+    assert!(!debug.contains('\x1b'));
+}
+
+#[test]
+fn insert_code_before_existing_marker() {
+    let mut cs = ColoredString::new();
+
+    cs.push_str("ab");
+    cs.set_fg(&Color::Red);
+    cs.push_str("cd");
+    cs.insert_code_at(1, 4);
+
+    assert_eq!(cs.colored(), "a\x1b[4mb\x1b[31mcd\x1b[0m");
+}
+
+#[test]
+fn insert_code_at_same_index_keeps_order() {
+    let mut cs = ColoredString::new();
+
+    cs.set_fg(&Color::Red);
+    cs.push_str("ab");
+    cs.insert_code_at(0, 4);
+
+    assert_eq!(cs.colored(), "\x1b[31;4mab\x1b[0m");
+}
+
+#[test]
+fn codes_without_text_render_empty() {
+    let mut cs = ColoredString::new();
+
+    cs.set_fg(&Color::Red);
+
+    assert_eq!(cs.colored(), "");
+    assert_eq!(cs.to_string(), "");
+}
+
+#[test]
+fn push_str_escaped_neutralizes_escapes() {
+    let mut cs = ColoredString::new();
+
+    cs.push_str_escaped("\x1b[31mhi\u{9b}0m\n");
+
+    assert_eq!(cs.raw(), "␛[31mhi\\u{9b}0m\n");
+    assert!(!cs.colored().contains('\x1b'));
+}
+
+#[test]
+fn width_counts_chars() {
+    let mut cs = ColoredString::new();
+
+    cs.set_fg(&Color::Red);
+    cs.push_str("héllo");
+
+    assert_eq!(cs.width(), 5);
+}
+
+#[cfg(feature = "unicode-width")]
+#[test]
+fn display_width_of_wide_and_combining_chars() {
+    let mut cs = ColoredString::new();
+
+    cs.set_fg(&Color::Red);
+    cs.push_str("a😀字e\u{301}");
+
+    assert_eq!(cs.width(), 5);
+    assert_eq!(cs.display_width(), 6);
+}
+
+#[test]
+fn truncate_drops_codes_of_removed_text() {
+    let mut cs = ColoredString::new();
+
+    cs.set_fg(&Color::Red);
+    cs.push_str("héllo");
+    cs.set_fg(&Color::Blue);
+    cs.push_str(" world");
+    cs.truncate(4);
+
+    assert_eq!(cs.raw(), "héll");
+    assert_eq!(cs.colored(), "\x1b[31mhéll\x1b[0m");
+}
+
+#[cfg(all(feature = "unicode-width", feature = "unicode-segmentation"))]
+#[test]
+fn truncate_width_keeps_wide_chars_whole() {
+    let mut cs = ColoredString::new();
+
+    cs.set_fg(&Color::Red);
+    cs.push_str("ab字e\u{301}f");
+    cs.truncate_width(4);
+
+    assert_eq!(cs.raw(), "ab字");
+
+    cs.truncate_width(3);
+
+    assert_eq!(cs.raw(), "ab");
+}
+
+#[test]
+fn split_at_keeps_color_on_both_halves() {
+    let mut cs = ColoredString::new();
+
+    cs.push_str("> ");
+    cs.set_fg(&Color::Red);
+    cs.set_underline(true);
+    cs.push_str("hello");
+
+    let (left, right) = cs.split_at(4);
+
+    assert_eq!(left.colored(), "> \x1b[31;4mhe\x1b[0m");
+    assert_eq!(right.colored(), "\x1b[31;4mllo\x1b[0m");
+}
+
+#[test]
+fn split_at_carries_only_codes_in_effect() {
+    let mut cs = ColoredString::new();
+
+    cs.set_fg(&Color::Red);
+    cs.push_str("a");
+    cs.set_fg(&Color::Blue);
+    cs.set_bold(true);
+    cs.push_str("b");
+    cs.set_bold(false);
+    cs.push_str("cd");
+
+    let (_, right) = cs.split_at(3);
+
+    assert_eq!(right.markers().collect::<Vec<_>>(), [(0, &[34][..])]);
+}
+
+#[test]
+fn split_at_bounds() {
+    let mut cs = ColoredString::new();
+
+    cs.set_fg(&Color::Red);
+    cs.push_str("hé");
+
+    let (left, right) = cs.split_at(0);
+
+    assert_eq!(left.colored(), "");
+    assert_eq!(right.colored(), cs.colored());
+
+    let (left, right) = cs.split_at(2);
+
+    assert_eq!(left.colored(), cs.colored());
+    assert_eq!(right.colored(), "");
+}
+
+fn fragment(text: &str, color: Color) -> ColoredString {
+    let mut cs = ColoredString::new();
+
+    cs.set_fg(&color);
+    cs.push_str(text);
+
+    cs
+}
+
+#[test]
+fn push_colored_restores_style() {
+    let mut cs = fragment("a", Color::Red);
+
+    cs.push_colored(&fragment("b", Color::Blue));
+    cs.push_str("c");
+
+    assert_eq!(cs.colored(), "\x1b[31ma\x1b[34mb\x1b[31mc\x1b[0m");
+}
+
+#[test]
+fn trailing_codes_are_not_rendered() {
+    let mut cs = fragment("a", Color::Red);
+
+    cs.push_colored(&fragment("b", Color::Blue));
+
+    assert_eq!(cs.colored(), "\x1b[31ma\x1b[34mb\x1b[0m");
+
+    let mut cs = fragment("a", Color::Red);
+
+    cs.push_newline();
+
+    assert_eq!(cs.colored(), "\x1b[31ma\x1b[0m\n");
+
+    let mut cs = ColoredString::from("plain");
+
+    cs.set_fg(&Color::Red);
+
+    assert_eq!(cs.colored(), "plain");
+}
+
+#[test]
+fn join_keeps_fragment_colors() {
+    let parts = [
+        fragment("a", Color::Red),
+        fragment("b", Color::Green),
+        fragment("c", Color::Blue),
+    ];
+
+    let joined = colost::join(&parts, ", ");
+
+    assert_eq!(joined.raw(), "a, b, c");
+    assert!(joined
+        .colored()
+        .starts_with("\x1b[31ma\x1b[0m, \x1b[32mb\x1b[0m, \x1b[34mc"));
+
+    let joined = colost::join_colored(&parts[..2], &fragment("|", Color::White));
+
+    assert!(joined.colored().starts_with("\x1b[31ma\x1b[37m|\x1b[32mb"));
+}
+
+#[test]
+fn display_aligns_on_visible_width() {
+    let cs = fragment("hi", Color::Red);
+
+    assert_eq!(format!("{:>10}", cs), "        \x1b[31mhi\x1b[0m");
+    assert_eq!(format!("{:<5}|", cs), "\x1b[31mhi\x1b[0m   |");
+    assert_eq!(format!("{:*^7}", cs), "**\x1b[31mhi\x1b[0m***");
+    assert_eq!(format!("{:1}", cs), cs.colored());
+}
+
+#[cfg(feature = "unicode-width")]
+#[test]
+fn display_aligns_on_cell_width() {
+    let cs = fragment("字", Color::Red);
+
+    assert_eq!(format!("{:>4}", cs), "  \x1b[31m字\x1b[0m");
+}
+
+#[test]
+fn pad_to_aligns_content() {
+    let cs = fragment("hi", Color::Red);
+
+    assert_eq!(
+        cs.pad_to(5, colost::Align::Right, '.').colored(),
+        "...\x1b[31mhi\x1b[0m"
+    );
+    assert_eq!(cs.pad_to(5, colost::Align::Left, ' ').raw(), "hi   ");
+}
+
+#[test]
+fn center_ljust_rjust() {
+    let cs = fragment("hi", Color::Red);
+
+    assert_eq!(cs.center(6, '-').colored(), "--\x1b[31mhi\x1b[0m--");
+    assert_eq!(cs.ljust(4, ' ').colored(), "\x1b[31mhi\x1b[0m  ");
+    assert_eq!(cs.rjust(4, ' ').colored(), "  \x1b[31mhi\x1b[0m");
+    assert_eq!(cs.rjust(1, ' ').colored(), cs.colored());
+}
+
+#[test]
+fn expand_tabs_keeps_color_in_place() {
+    let mut cs = ColoredString::new();
+
+    cs.push('\t');
+    cs.set_fg(&Color::Red);
+    cs.push_str("ab\tc\n\td");
+
+    let expanded = cs.expand_tabs(4);
+
+    assert_eq!(expanded.raw(), "    ab  c\n    d");
+    assert_eq!(expanded.colored(), "    \x1b[31mab  c\n    d\x1b[0m");
+    assert_eq!(cs.expand_tabs(0).raw(), "abc\nd");
+}
+
+#[test]
+fn wrap_words_breaks_at_spaces() {
+    let mut cs = ColoredString::new();
+
+    cs.push_str("the quick ");
+    cs.set_fg(&Color::Red);
+    cs.push_str("brown fox  jumps");
+
+    let lines = cs.wrap_words(10);
+    let raws: Vec<String> = lines.iter().map(ColoredString::raw).collect();
+
+    assert_eq!(raws, ["the quick", "brown fox", "jumps"]);
+    assert_eq!(lines[0].colored(), "the quick");
+    assert_eq!(lines[1].colored(), "\x1b[31mbrown fox\x1b[0m");
+    assert_eq!(lines[2].colored(), "\x1b[31mjumps\x1b[0m");
+}
+
+#[test]
+fn wrap_words_edge_cases() {
+    let mut cs = ColoredString::new();
+
+    cs.push_str("a  b abcdefgh\n\nc");
+
+    let raws: Vec<String> = cs.wrap_words(4).iter().map(ColoredString::raw).collect();
+
+    assert_eq!(raws, ["a  b", "abcd", "efgh", "", "c"]);
+}
+
+#[test]
+fn plain_and_monochrome_drop_styling() {
+    let mut cs = fragment("hi", Color::Red);
+
+    cs.set_underline(true);
+    cs.push_str(" there");
+
+    assert_eq!(cs.plain(), "hi there");
+    assert_eq!(cs.monochrome(), "hi there");
+}
+
+#[test]
+fn colored_multiline_styles_each_line() {
+    let mut cs = ColoredString::new();
+
+    cs.push_str("> ");
+    cs.set_fg(&Color::Red);
+    cs.push_str("one\ntwo\n");
+
+    assert_eq!(
+        cs.colored_multiline(),
+        "> \x1b[31mone\x1b[0m\n\x1b[31mtwo\x1b[0m\n"
+    );
+}
+
+#[test]
+fn render_parts_concatenate_to_colored() {
+    let mut cs = ColoredString::new();
+
+    cs.push_str("a");
+    cs.set_fg(&Color::BrightRed);
+    cs.set_bg(&Color::Blue);
+    cs.push_str("bc");
+    cs.reset();
+    cs.push_str("d");
+
+    let parts: Vec<&str> = cs.render_parts().collect();
+
+    assert_eq!(parts.concat(), cs.colored());
+    assert_eq!(cs.colored(), "a\x1b[91;44mbc\x1b[0md");
+    assert_eq!(
+        ColoredString::new().render_parts().collect::<Vec<_>>(),
+        Vec::<&str>::new()
+    );
+}
+
+#[test]
+fn set_color_emits_one_escape() {
+    let mut cs = ColoredString::new();
+
+    cs.set_color(&Color::Red, &Color::Blue);
+    cs.push_str("hi");
+
+    assert_eq!(cs.colored(), "\x1b[31;44mhi\x1b[0m");
+}
+
+#[test]
+fn chars_iterate_raw_content() {
+    let cs = fragment("héllo wörld", Color::Red);
+
+    assert_eq!(cs.chars().count(), 11);
+    assert_eq!(cs.char_indices().nth(2), Some((3, 'l')));
+}
+
+#[test]
+fn find_and_affixes_ignore_styling() {
+    let mut cs = fragment("héllo ", Color::Red);
+
+    cs.set_fg(&Color::Blue);
+    cs.push_str("world");
+
+    assert_eq!(cs.find("world"), Some(7));
+    assert_eq!(cs.find("o w"), Some(5));
+    assert_eq!(cs.find("xyz"), None);
+    assert!(cs.starts_with("hé"));
+    assert!(cs.ends_with("ld"));
+    assert!(!cs.ends_with("\x1b[0m"));
+}
+
+#[test]
+fn push_display_values() {
+    let mut cs = ColoredString::new();
+
+    cs.set_fg(&Color::Red);
+    cs.push_display(&42);
+    cs.push(' ');
+    cs.push_display(&1.5);
+
+    assert_eq!(cs.colored(), "\x1b[31m42 1.5\x1b[0m");
+}
+
+#[test]
+fn write_macro_pushes_text() {
+    use std::fmt::Write;
+
+    let mut cs = ColoredString::new();
+
+    cs.set_fg(&Color::Red);
+    write!(cs, "{}-{}", 1, 2).unwrap();
+
+    assert_eq!(cs.raw(), "1-2");
+}
+
+#[test]
+fn push_cow_borrowed_and_owned() {
+    use std::borrow::Cow;
+
+    let mut cs = ColoredString::new();
+
+    cs.set_fg(&Color::Red);
+    cs.push_cow(Cow::Owned(String::from("ab")));
+    cs.push_cow(Cow::Borrowed("cd"));
+    cs.push_cow(Cow::Owned(String::from("ef")));
+
+    assert_eq!(cs.colored(), "\x1b[31mabcdef\x1b[0m");
+}
+
+#[test]
+fn push_raw_sgr_emits_codes_verbatim() {
+    let mut cs = ColoredString::new();
+
+    cs.set_fg(&Color::Red);
+    cs.push_raw_sgr(&[38, 2, 255, 0, 10]);
+    cs.push_str("hi");
+    cs.push_raw_sgr(&[11]);
+    cs.push_str("!");
+
+    assert_eq!(cs.colored(), "\x1b[38;2;255;0;10mhi\x1b[11m!\x1b[0m");
+    assert!(format!("{:?}", cs).contains("(0, 38;2;255;0;10)"));
+}
+
+#[test]
+fn sgr_codes_per_marker() {
+    let mut cs = ColoredString::new();
+
+    cs.set_fg(&Color::Red);
+    cs.set_bg(&Color::Blue);
+    cs.push_str("hi");
+    cs.push_raw_sgr(&[38, 5, 200]);
+    cs.push_str("!");
+
+    assert_eq!(cs.sgr_codes(), [vec![31], vec![44], vec![38, 5, 200]]);
+}
+
+#[test]
+fn iterate_segments() {
+    let mut cs = ColoredString::new();
+
+    cs.push_str("a");
+    cs.set_fg(&Color::Red);
+    cs.push_str("b");
+    cs.set_bg(&Color::Blue);
+    cs.push_str("c");
+    cs.reset();
+    cs.push_str("d");
+
+    let borrowed: Vec<(&str, Vec<u8>)> = (&cs).into_iter().collect();
+
+    assert_eq!(
+        borrowed,
+        [
+            ("a", vec![]),
+            ("b", vec![31]),
+            ("c", vec![31, 44]),
+            ("d", vec![])
+        ]
+    );
+
+    let mut content = String::new();
+
+    for (text, _codes) in cs {
+        content += &text;
+    }
+
+    assert_eq!(content, "abcd");
+}
+
+#[test]
+fn segments_leave_out_superseded_codes() {
+    let mut cs = ColoredString::new();
+
+    cs.set_fg(&Color::Red);
+    cs.set_bold(true);
+    cs.push_str("a");
+    cs.set_fg(&Color::Blue);
+    cs.set_bold(false);
+    cs.push_str("b");
+
+    let segments: Vec<(&str, Vec<u8>)> = cs.segments().collect();
+
+    assert_eq!(segments, [("a", vec![31, 1]), ("b", vec![34])]);
+}
+
+#[test]
+fn ellipsize_long_label() {
+    let cs = fragment("a long label", Color::Red);
+
+    assert_eq!(cs.ellipsize(20, "…").colored(), cs.colored());
+    assert_eq!(cs.ellipsize(7, "…").colored(), "\x1b[31ma long\x1b[0m…");
+    assert_eq!(cs.ellipsize(7, "…").width(), 7);
+    assert_eq!(cs.ellipsize(2, "...").colored(), "..");
+}
+
+#[test]
+fn change_case_keeps_markers() {
+    let mut cs = fragment("ɐß", Color::Red);
+
+    cs.set_fg(&Color::Blue);
+    cs.push_str("é!");
+
+    assert_eq!(cs.to_uppercase().colored(), "\x1b[31mⱯSS\x1b[34mÉ!\x1b[0m");
+    assert_eq!(
+        cs.to_uppercase().to_lowercase().colored(),
+        "\x1b[31mɐss\x1b[34mé!\x1b[0m"
+    );
+}
+
+#[test]
+fn mask_keeps_width_and_colors() {
+    let mut cs = fragment("séc", Color::Red);
+
+    cs.set_fg(&Color::Blue);
+    cs.push_str("ré");
+
+    assert_eq!(cs.mask('*').colored(), "\x1b[31m***\x1b[34m**\x1b[0m");
+    assert_eq!(cs.mask('•').width(), 5);
+    assert_eq!(fragment("a\nb", Color::Red).mask('*').raw(), "*\n*");
+}
+
+#[test]
+fn contains_across_styled_runs() {
+    let mut cs = fragment("hel", Color::Red);
+
+    cs.set_fg(&Color::Blue);
+    cs.push_str("lo");
+
+    assert!(cs.contains("hello"));
+    assert!(cs.contains("ll"));
+    assert!(!cs.contains("31m"));
+}
+
+#[test]
+fn colored_len_matches_output() {
+    let mut cs = ColoredString::new();
+
+    assert_eq!(cs.colored_len(), 0);
+
+    cs.push_str("plain ");
+
+    assert_eq!(cs.colored_len(), cs.colored().len());
+
+    cs.set_fg(&Color::Red);
+    cs.set_bg(&Color::BrightBlue);
+    cs.push_str("héllo");
+    cs.push_raw_sgr(&[38, 2, 255, 0, 7]);
+    cs.push_str(" world");
+    cs.reset();
+
+    assert_eq!(cs.colored_len(), cs.colored().len());
+}
+
+#[test]
+fn push_ascii_matches_push_str() {
+    let mut ascii = ColoredString::new();
+    let mut string = ColoredString::new();
+
+    ascii.set_fg(&Color::Red);
+    string.set_fg(&Color::Red);
+
+    // SAFETY: the bytes are ASCII.
+    unsafe { ascii.push_ascii(b"level=info count=42") };
+    string.push_str("level=info count=42");
+
+    assert_eq!(ascii.colored(), string.colored());
+}
+
+#[test]
+fn invert_colors_swaps_fg_and_bg() {
+    let mut cs = ColoredString::new();
+
+    cs.set_color(&Color::Red, &Color::Blue);
+    cs.set_underline(true);
+    cs.push_str("hi");
+    cs.push_raw_sgr(&[38, 5, 40, 48, 2, 30, 40, 100, 39]);
+    cs.push_str("!");
+    cs.invert_colors();
+
+    assert_eq!(
+        cs.colored(),
+        "\x1b[41;34;4mhi\x1b[38;2;30;40;100;49m!\x1b[0m"
+    );
+}
+
+#[test]
+fn to_grayscale_converts_colors() {
+    let mut cs = ColoredString::new();
+
+    cs.set_fg(&Color::Red);
+    cs.set_bg(&Color::BrightWhite);
+    cs.push_str("a");
+    cs.set_fg_rgb(0, 255, 0);
+    cs.set_underline(true);
+    cs.push_str("b");
+    cs.to_grayscale();
+
+    assert_eq!(
+        cs.colored(),
+        "\x1b[38;2;44;44;44;48;2;255;255;255ma\x1b[38;2;182;182;182;4mb\x1b[0m"
+    );
+    assert_eq!(colost::luminance((0, 0, 0)), 0);
+    assert_eq!(colost::luminance((255, 255, 255)), 255);
+}
+
+#[test]
+fn repeated_setter_is_merged() {
+    let mut cs = ColoredString::new();
+
+    cs.set_fg(&Color::Red);
+    cs.set_fg(&Color::Red);
+    cs.push_str("hi");
+
+    assert_eq!(cs.colored(), "\x1b[31mhi\x1b[0m");
+    assert_eq!(cs.sgr_codes().len(), 1);
+}
+
+#[test]
+fn code_already_in_effect_is_not_emitted() {
+    let mut cs = fragment("ab", Color::Red);
+
+    cs.set_fg(&Color::Red);
+    cs.push_str("cd");
+    cs.set_underline(true);
+    cs.set_fg(&Color::Red);
+    cs.push_str("ef");
+    cs.set_fg_rgb(1, 2, 3);
+    cs.push_str("g");
+    cs.set_fg_rgb(1, 2, 3);
+    cs.push_str("h");
+
+    assert_eq!(
+        cs.colored(),
+        "\x1b[31mabcd\x1b[4mef\x1b[38;2;1;2;3mgh\x1b[0m"
+    );
+}
+
+#[test]
+fn explicit_final_reset_is_not_doubled() {
+    let mut cs = fragment("hi", Color::Red);
+
+    cs.reset();
+
+    assert_eq!(cs.colored(), "\x1b[31mhi\x1b[0m");
+
+    cs.push_str("!");
+
+    assert_eq!(cs.colored(), "\x1b[31mhi\x1b[0m!");
+
+    let mut cs = ColoredString::new();
+
+    cs.push_raw_sgr(&[73]);
+    cs.push_str("hi");
+
+    assert_eq!(cs.colored(), "\x1b[73mhi\x1b[0m");
+}
+
+#[test]
+fn from_conversions() {
+    assert_eq!(ColoredString::from('x').raw_str(), "x");
+    assert_eq!(ColoredString::from("abc").raw_str(), "abc");
+    assert_eq!(ColoredString::from(String::from("é")).colored(), "é");
+}
+
+#[test]
+fn sort_by_visible_text() {
+    let mut list: Vec<ColoredString> = vec![
+        fragment("pear", Color::Green),
+        fragment("apple", Color::Red),
+        ColoredString::from("banana"),
+    ];
+
+    list.sort();
+
+    let raws: Vec<&str> = list.iter().map(ColoredString::raw_str).collect();
+
+    assert_eq!(raws, ["apple", "banana", "pear"]);
+    assert!(fragment("a", Color::Red) != ColoredString::from("a"));
+    assert!(ColoredString::from("a") < fragment("b", Color::Red));
+}
+
+#[test]
+fn boxed_aligns_borders() {
+    let mut cs = fragment("hello", Color::Red);
+
+    cs.reset();
+    cs.push_str("\nhi");
+
+    let boxed = cs.boxed(&Color::Blue);
+
+    assert_eq!(boxed.raw(), "┌───────┐\n│ hello │\n│ hi    │\n└───────┘");
+    assert_eq!(
+        boxed.colored().lines().nth(1),
+        Some("\x1b[34m│\x1b[0m \x1b[31mhello\x1b[0m \x1b[34m│\x1b[0m")
+    );
+}
+
+#[test]
+fn zero_width_toggle_is_collapsed() {
+    let mut cs = ColoredString::new();
+
+    cs.set_underline(true);
+    cs.push_str("ab");
+    cs.set_underline(false);
+    cs.set_underline(true);
+    cs.push_str("cd");
+    cs.set_underline(false);
+    cs.push_str("ef");
+
+    assert_eq!(cs.colored(), "\x1b[4mabcd\x1b[24mef");
+}
+
+#[test]
+fn reset_colors_keeps_attributes() {
+    let mut cs = ColoredString::new();
+
+    cs.push_raw_sgr(&[1]);
+    cs.set_color(&Color::Red, &Color::Blue);
+    cs.push_str("ab");
+    cs.reset_with(ResetMode::Colors);
+    cs.push_str("c");
+
+    assert_eq!(cs.colored(), "\x1b[1;31;44mab\x1b[39;49mc\x1b[0m");
+
+    cs.reset_with(ResetMode::Full);
+    cs.push_str("d");
+
+    assert_eq!(cs.colored(), "\x1b[1;31;44mab\x1b[39;49mc\x1b[0md");
+}
+
+#[test]
+fn colorize_lines_uses_the_callback_color() {
+    let lines = ["starting", "ERROR: disk full", "done"].map(String::from);
+    let colored: Vec<String> = colost::colorize_lines(lines.into_iter(), |line| {
+        if line.contains("ERROR") {
+            Color::Red
+        } else {
+            Color::Green
+        }
+    })
+    .map(|cs| cs.colored())
+    .collect();
+
+    assert_eq!(
+        colored,
+        [
+            "\x1b[32mstarting\x1b[0m",
+            "\x1b[31mERROR: disk full\x1b[0m",
+            "\x1b[32mdone\x1b[0m"
+        ]
+    );
+}
+
+#[test]
+fn indent_keeps_line_colors() {
+    let mut cs = ColoredString::new();
+
+    cs.push_str("a ");
+    cs.set_fg(&Color::Red);
+    cs.push_str("b\nc");
+    cs.reset();
+    cs.push_str(" d");
+
+    let indented = cs.indent(2);
+
+    assert_eq!(indented.raw(), "  a b\n  c d");
+    assert_eq!(
+        indented.colored(),
+        "  a \x1b[31mb\x1b[0m\n  \x1b[31mc\x1b[0m d"
+    );
+}
+
+#[test]
+fn display_matches_colored() {
+    let mut cs = ColoredString::new();
+
+    cs.push_str("a");
+    cs.set_color(&Color::Red, &Color::Black);
+    cs.push_str("bc");
+    cs.push_raw_sgr(&[38, 2, 1, 2, 3]);
+    cs.push_str("d");
+
+    assert_eq!(format!("{}", cs), cs.colored());
+    assert_eq!(format!("{:>6}", cs), format!("  {}", cs.colored()));
+}
+
+#[test]
+fn set_fg_bright_uses_bright_codes() {
+    let mut cs = ColoredString::new();
+
+    cs.set_fg_bright(&Color::Red);
+    cs.push_str("a");
+    cs.set_fg_bright(&Color::BrightBlue);
+    cs.push_str("b");
+
+    assert_eq!(cs.colored(), "\x1b[91ma\x1b[94mb\x1b[0m");
+}
+
+#[test]
+fn set_font_selects_alternate_font() {
+    let mut cs = ColoredString::new();
+
+    cs.set_font(2);
+    cs.push_str("a");
+    cs.set_font(0);
+    cs.push_str("b");
+
+    assert_eq!(cs.colored(), "\x1b[12ma\x1b[10mb");
+}
+
+#[test]
+#[should_panic]
+fn set_font_rejects_out_of_range_index() {
+    ColoredString::new().set_font(10);
+}
+
+#[test]
+fn try_set_font_checks_index() {
+    let mut cs = ColoredString::new();
+
+    assert_eq!(
+        cs.try_set_font(10),
+        Err(colost::StyleError::ComponentOutOfRange { value: 10, max: 9 })
+    );
+    assert_eq!(cs.try_set_font(9), Ok(()));
+
+    cs.push_str("a");
+
+    assert_eq!(cs.colored(), "\x1b[19ma\x1b[0m");
+}
+
+#[test]
+fn set_proportional_spacing_toggles() {
+    let mut cs = ColoredString::new();
+
+    cs.set_proportional_spacing(true);
+    cs.push_str("a");
+    cs.set_proportional_spacing(false);
+    cs.push_str("b");
+
+    assert_eq!(cs.colored(), "\x1b[26ma\x1b[50mb");
+}
+
+#[test]
+fn escape_count_counts_coalesced_sequences() {
+    let mut cs = ColoredString::new();
+
+    cs.set_fg(&Color::Red);
+    cs.set_bg(&Color::Blue);
+    cs.push_str("a\x1b[b");
+    cs.set_fg(&Color::Green);
+    cs.push_str("c");
+
+    assert_eq!(cs.colored(), "\x1b[31;44ma\x1b[b\x1b[32mc\x1b[0m");
+    assert_eq!(cs.escape_count(), 3);
+    assert_eq!(ColoredString::from("a").escape_count(), 0);
+}
+
+#[test]
+fn replace_styled_splices_colored_fragment() {
+    let mut cs = ColoredString::new();
+
+    cs.set_fg(&Color::Green);
+    cs.push_str("hi {name}, bye {name}");
+    cs.reset();
+    cs.push_str("!");
+    cs.replace_styled("{name}", &fragment("bob", Color::Red));
+
+    assert_eq!(cs.raw(), "hi bob, bye bob!");
+    assert_eq!(
+        cs.colored(),
+        "\x1b[32mhi \x1b[31mbob\x1b[32m, bye \x1b[31mbob\x1b[0m!"
+    );
+}
+
+#[test]
+fn has_color_matches_rendering() {
+    let mut cs = ColoredString::from("plain");
+
+    assert!(!cs.has_color());
+
+    cs.set_fg(&Color::Red);
+
+    assert_eq!(cs.colored(), cs.raw());
+    assert!(!cs.has_color());
+
+    cs.push_str("red");
+
+    assert!(cs.has_color());
+
+    let mut cs = ColoredString::new();
+
+    cs.reset();
+    cs.push_str("x");
+
+    assert_eq!(cs.colored(), cs.raw());
+    assert!(!cs.has_color());
+}
+
+#[test]
+fn map_raw_keeps_codes_only_for_same_length() {
+    let mut cs = ColoredString::new();
+
+    cs.push_str("pin ");
+    cs.set_fg(&Color::Red);
+    cs.push_str("1234");
+
+    let redacted = cs.clone().map_raw(|raw| raw.replace(char::is_numeric, "*"));
+
+    assert_eq!(redacted.colored(), "pin \x1b[31m****\x1b[0m");
+
+    let shortened = cs.map_raw(|raw| raw.replace("1234", "-"));
+
+    assert_eq!(shortened.colored(), "pin -");
+}
+
+#[test]
+fn with_window_title_prepends_osc() {
+    let cs = fragment("hi", Color::Red);
+
+    assert_eq!(
+        cs.with_window_title("build\x07 done"),
+        "\x1b]2;build done\x07\x1b[31mhi\x1b[0m"
+    );
+}
+
+#[test]
+fn colored_with_leading_reset_starts_clean() {
+    let cs = fragment("hi", Color::Red);
+
+    assert_eq!(cs.colored_with_leading_reset(), "\x1b[0m\x1b[31mhi\x1b[0m");
+    assert_eq!(cs.colored(), "\x1b[31mhi\x1b[0m");
+    assert_eq!(
+        ColoredString::from("hi").colored_with_leading_reset(),
+        "\x1b[0mhi"
+    );
+}
+
+#[test]
+fn colored_with_introducer_swaps_csi() {
+    let mut cs = fragment("a", Color::Red);
+
+    cs.set_bg(&Color::Blue);
+    cs.push_str("b\u{9b}");
+
+    assert_eq!(cs.colored_with_introducer(false), cs.colored().as_bytes());
+    assert_eq!(
+        cs.colored_with_introducer(true),
+        b"\x9b31ma\x9b44mb\xc2\x9b\x9b0m"
+    );
+}
+
+#[test]
+fn markers_report_index_and_codes() {
+    let mut cs = fragment("ab", Color::Red);
+
+    cs.set_fg_rgb(1, 2, 3);
+    cs.push_str("é");
+    cs.reset();
+
+    let markers: Vec<(usize, &[u8])> = cs.markers().collect();
+
+    assert_eq!(
+        markers,
+        [(0, &[31][..]), (2, &[38, 2, 1, 2, 3][..]), (4, &[0][..])]
+    );
+}
+
+#[test]
+fn push_newline_styles_each_line() {
+    let mut cs = fragment("a", Color::Red);
+
+    cs.set_underline(true);
+    cs.push_newline();
+    cs.push_str("b");
+    cs.reset();
+    cs.push_newline();
+    cs.push_str("c");
+
+    assert_eq!(cs.raw(), "a\nb\nc");
+    assert_eq!(cs.colored(), "\x1b[31ma\x1b[0m\n\x1b[31;4mb\x1b[0m\nc");
+
+    let mut cs = ColoredString::new();
+
+    cs.set_bold(true);
+    cs.push_str("a");
+    cs.set_bold(false);
+    cs.push_newline();
+    cs.push_str("b");
+
+    assert_eq!(cs.markers().count(), 2);
+    assert_eq!(cs.colored(), "\x1b[1ma\x1b[22m\nb");
+}
+
+#[test]
+fn reverse_chars_keeps_char_colors() {
+    let mut cs = fragment("aé", Color::Red);
+
+    cs.set_bg(&Color::Blue);
+    cs.push_str("b");
+    cs.reset();
+    cs.push_str("cd");
+
+    let reversed = cs.reverse_chars();
+
+    assert_eq!(reversed.raw(), "dcbéa");
+    assert_eq!(reversed.colored(), "dc\x1b[31;44mb\x1b[0;31méa\x1b[0m");
+}
+
+#[test]
+fn set_bg_bright_uses_bright_codes() {
+    let mut cs = ColoredString::new();
+
+    cs.set_fg(&Color::Black);
+    cs.set_bg_bright(&Color::Blue);
+    cs.push_str("a");
+    cs.set_bg_bright(&Color::BrightRed);
+    cs.push_str("b");
+
+    assert_eq!(cs.colored(), "\x1b[30;104ma\x1b[101mb\x1b[0m");
+}
+
+#[test]
+fn validate_reports_reset_mid_word() {
+    let mut cs = fragment("err", Color::Red);
+
+    cs.reset();
+    cs.push_str("or: ");
+    cs.set_fg(&Color::Blue);
+    cs.push_str("disk");
+    cs.reset();
+    cs.push_str(" full");
+
+    assert_eq!(
+        cs.validate(),
+        Err(vec![StyleWarning::ResetMidWord { index: 3 }])
+    );
+    assert_eq!(fragment("ok", Color::Green).validate(), Ok(()));
+
+    let mut cs = fragment("err", Color::Red);
+
+    cs.reset();
+    cs.push_str(": details");
+
+    assert_eq!(cs.validate(), Ok(()));
+}
+
+#[test]
+fn codes_around_empty_text_share_one_escape() {
+    let mut cs = ColoredString::new();
+
+    cs.push_str("a");
+    cs.set_fg(&Color::Red);
+    cs.push_str("");
+    cs.set_bg(&Color::Blue);
+    cs.push_colored(&ColoredString::new());
+    cs.push_raw_sgr(&[1]);
+    cs.push_str("b");
+
+    assert_eq!(cs.colored(), "a\x1b[31;44;1mb\x1b[0m");
+    assert_eq!(cs.escape_count(), 2);
+}
+
+#[test]
+fn truncate_start_keeps_the_tail_style() {
+    let mut cs = ColoredString::new();
+
+    cs.push_str("/home/");
+    cs.set_fg(&Color::Blue);
+    cs.push_str("user/");
+    cs.set_underline(true);
+    cs.push_str("file.rs");
+    cs.reset();
+    cs.truncate_start(9);
+
+    assert_eq!(cs.raw(), "r/file.rs");
+    assert_eq!(cs.colored(), "\x1b[34mr/\x1b[4mfile.rs\x1b[0m");
+
+    cs.truncate_start(20);
+
+    assert_eq!(cs.raw(), "r/file.rs");
+}
+
+#[test]
+fn reset_attribute_disables_only_it() {
+    let mut cs = ColoredString::new();
+
+    cs.set_fg(&Color::Red);
+    cs.set_bold(true);
+    cs.set_underline(true);
+    cs.push_str("a");
+    cs.reset_attribute(Attribute::Underline);
+    cs.push_str("b");
+    cs.reset_attribute(Attribute::Bold);
+    cs.push_str("c");
+
+    assert_eq!(cs.colored(), "\x1b[31;1;4ma\x1b[24mb\x1b[22mc\x1b[0m");
+}
+
+#[test]
+fn attribute_setters_use_standard_codes() {
+    let mut cs = ColoredString::new();
+
+    cs.set_faint(true);
+    cs.set_fast_blink(true);
+    cs.push_str("a");
+    cs.set_faint(false);
+    cs.set_fast_blink(false);
+    cs.push_str("b");
+
+    assert_eq!(cs.colored(), "\x1b[2;6ma\x1b[22;25mb");
+}
+
+#[test]
+fn colored_annotated_names_the_codes() {
+    let mut cs = fragment("error", Color::Red);
+
+    cs.set_bold(true);
+    cs.set_bg_rgb(1, 2, 3);
+    cs.push_str("!");
+    cs.set_bold(false);
+    cs.set_italic(true);
+    cs.set_fg_256(40);
+    cs.push_str("?");
+    cs.reset();
+    cs.push_str(" ok");
+
+    assert_eq!(
+        cs.colored_annotated(),
+        "<fg:red>error<bold><bg:rgb(1,2,3)>!<normal_intensity><italic><fg:40>?<reset> ok"
+    );
+    assert_eq!(
+        fragment("a", Color::BrightBlue).colored_annotated(),
+        "<fg:bright_blue>a<reset>"
+    );
+}
+
+#[test]
+fn disabling_bold_keeps_faint() {
+    let mut cs = ColoredString::new();
+
+    cs.set_bold(true);
+    cs.push_str("a");
+    cs.set_faint(true);
+    cs.push_str("b");
+    cs.set_bold(false);
+    cs.push_str("c");
+    cs.set_faint(false);
+    cs.push_str("d");
+
+    assert_eq!(cs.colored(), "\x1b[1ma\x1b[2mb\x1b[22;2mc\x1b[22md");
+}
+
+#[test]
+fn proportional_spacing_keeps_fast_blink() {
+    let mut cs = ColoredString::new();
+
+    cs.set_fast_blink(true);
+    cs.set_proportional_spacing(true);
+    cs.push_str("a");
+    cs.set_slow_blink(false);
+    cs.push_str("b");
+
+    assert_eq!(cs.markers().last(), Some((1, &[25, 6][..])));
+    assert_eq!(cs.colored(), "\x1b[6;26mab\x1b[0m");
+}
+
+#[test]
+fn every_attribute_has_a_setter() {
+    let mut cs = ColoredString::new();
+
+    cs.set_reverse(true);
+    cs.set_conceal(true);
+    cs.set_strike(true);
+    cs.push_str("a");
+    cs.set_reverse(false);
+    cs.set_conceal(false);
+    cs.set_strike(false);
+    cs.push_str("b");
+
+    assert_eq!(cs.colored(), "\x1b[7;8;9ma\x1b[27;28;29mb");
+}
+
+#[test]
+fn with_capacities_reserves_markers() {
+    let cs = ColoredString::with_capacities(16, 8);
+
+    assert!(cs.marker_capacity() >= 8);
+    assert_eq!(ColoredString::new().marker_capacity(), 0);
+}
+
+#[test]
+fn with_text_takes_owned_string() {
+    let mut text = String::with_capacity(64);
+
+    text.push_str("hello");
+
+    let ptr = text.as_ptr();
+    let mut cs = ColoredString::with_text(text);
+
+    assert_eq!(cs.capacity(), 64);
+    assert_eq!(cs.raw_str().as_ptr(), ptr);
+
+    cs.insert_code_at(0, 31);
+
+    assert_eq!(cs.colored(), "\x1b[31mhello\x1b[0m");
+    assert_eq!(ColoredString::with_text("hi").raw(), "hi");
+}
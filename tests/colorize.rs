@@ -0,0 +1,20 @@
+use colost::prelude::*;
+
+#[test]
+fn str_methods_chain() {
+    assert_eq!("x".red().colored(), "\x1b[31mx\x1b[0m");
+    assert_eq!(
+        String::from("ok").green().bold().underline().colored(),
+        "\x1b[32;1;4mok\x1b[0m"
+    );
+}
+
+#[test]
+fn colored_string_keeps_inner_styling() {
+    let mut cs = ColoredString::from("a");
+
+    cs.set_fg(&Color::Blue);
+    cs.push_str("b");
+
+    assert_eq!(cs.bg(Color::White).colored(), "\x1b[47ma\x1b[34mb\x1b[0m");
+}
@@ -0,0 +1,65 @@
+use colost::Color;
+use colost::ColoredString;
+use colost::DiffKind;
+use colost::DiffSpan;
+
+fn build(parts: &[(&str, Option<Color>)]) -> ColoredString {
+    let mut cs = ColoredString::new();
+
+    for (text, color) in parts {
+        match color {
+            Some(color) => cs.set_fg(color),
+            None => cs.reset(),
+        }
+
+        cs.push_str(text);
+    }
+
+    cs
+}
+
+#[test]
+fn identical_strings_have_no_diff() {
+    let cs = build(&[("hello ", None), ("world", Some(Color::Red))]);
+
+    assert_eq!(cs.diff(&cs), []);
+}
+
+#[test]
+fn one_colored_word_is_one_change() {
+    let old = build(&[
+        ("status: ", None),
+        ("ok", Some(Color::Green)),
+        (" done", None),
+    ]);
+    let new = build(&[
+        ("status: ", None),
+        ("ko", Some(Color::Red)),
+        (" done", None),
+    ]);
+
+    assert_eq!(
+        old.diff(&new),
+        [DiffSpan {
+            kind: DiffKind::Changed,
+            old: 8..10,
+            new: 8..10,
+        }]
+    );
+}
+
+#[test]
+fn added_and_removed_text() {
+    let old = build(&[("abc", None)]);
+    let new = build(&[("abxc", None)]);
+
+    assert_eq!(
+        old.diff(&new),
+        [DiffSpan {
+            kind: DiffKind::Added,
+            old: 2..2,
+            new: 2..3,
+        }]
+    );
+    assert_eq!(new.diff(&old)[0].kind, DiffKind::Removed);
+}
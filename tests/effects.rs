@@ -0,0 +1,79 @@
+use colost::Color;
+
+#[test]
+fn fade_blends_toward_background() {
+    let faded = colost::fade("abc", (200, 100, 0), 1.0, 0.0);
+
+    assert_eq!(
+        faded.sgr_codes(),
+        [
+            vec![38, 2, 200, 100, 0],
+            vec![38, 2, 100, 50, 0],
+            vec![38, 2, 0, 0, 0]
+        ]
+    );
+
+    let faded = colost::fade_on("ab", (0, 0, 0), (255, 255, 255), 0.0, 1.0);
+
+    assert_eq!(
+        faded.colored(),
+        "\x1b[38;2;255;255;255ma\x1b[38;2;0;0;0mb\x1b[0m"
+    );
+}
+
+#[test]
+fn two_color_palette_gradient() {
+    let gradient = colost::palette_gradient("abcde", &[Color::Black, Color::BrightWhite]);
+
+    assert_eq!(
+        gradient.sgr_codes(),
+        [
+            vec![38, 2, 0, 0, 0],
+            vec![38, 2, 64, 64, 64],
+            vec![38, 2, 128, 128, 128],
+            vec![38, 2, 191, 191, 191],
+            vec![38, 2, 255, 255, 255]
+        ]
+    );
+
+    let gradient = colost::palette_gradient("abc", &[Color::Red, Color::Green, Color::Blue]);
+
+    assert_eq!(
+        gradient.sgr_codes(),
+        [
+            vec![38, 2, 205, 0, 0],
+            vec![38, 2, 0, 205, 0],
+            vec![38, 2, 0, 0, 238]
+        ]
+    );
+    assert_eq!(colost::palette_gradient("ab", &[]).colored(), "ab");
+}
+
+#[test]
+fn over_composites_with_opacity() {
+    assert_eq!(
+        colost::over((255, 255, 255), (0, 0, 0), 0.5),
+        (128, 128, 128)
+    );
+    assert_eq!(colost::over((10, 20, 30), (200, 100, 0), 1.0), (10, 20, 30));
+    assert_eq!(
+        colost::over((10, 20, 30), (200, 100, 0), -1.0),
+        (200, 100, 0)
+    );
+}
+
+#[test]
+fn bar_fills_the_fraction_of_the_width() {
+    let half = colost::bar(0.5, 6, Color::Green, Color::BrightBlack);
+
+    assert_eq!(half.raw(), "███░░░");
+    assert_eq!(half.colored(), "\x1b[32m███\x1b[90m░░░\x1b[0m");
+    assert_eq!(
+        colost::bar(1.5, 3, Color::Green, Color::BrightBlack).raw(),
+        "███"
+    );
+    assert_eq!(
+        colost::bar(-1.0, 3, Color::Green, Color::BrightBlack).colored(),
+        "\x1b[90m░░░\x1b[0m"
+    );
+}
@@ -0,0 +1,147 @@
+use colost::Color;
+use colost::ColoredString;
+use colost::MarkupError;
+
+#[test]
+fn to_markup_wraps_styled_text_in_tags() {
+    let mut cs = ColoredString::new();
+
+    cs.set_fg(&Color::Red);
+    cs.push_str("error");
+    cs.reset();
+    cs.push_str(" ok");
+
+    assert_eq!(cs.to_markup(), "[red]error[/red] ok");
+}
+
+#[test]
+fn to_markup_nests_tags() {
+    let mut cs = ColoredString::new();
+
+    cs.set_bg(&Color::BrightBlue);
+    cs.push_str("a");
+    cs.push_raw_sgr(&[1]);
+    cs.push_str("b");
+    cs.push_raw_sgr(&[38, 5, 40]);
+    cs.push_str("c");
+    cs.reset();
+    cs.set_bg(&Color::BrightBlue);
+    cs.push_str("[d]\\");
+
+    assert_eq!(
+        cs.to_markup(),
+        "[on_bright_blue]a[bold]b[38;5;40]c[/38;5;40][/bold]\\[d\\]\\\\[/on_bright_blue]"
+    );
+}
+
+#[test]
+fn to_markup_closes_disabled_tags() {
+    let mut cs = ColoredString::new();
+
+    cs.set_fg(&Color::Red);
+    cs.push_str("a");
+    cs.set_bold(true);
+    cs.push_str("b");
+    cs.set_bold(false);
+    cs.push_str("c");
+    cs.set_bold(true);
+    cs.push_str("d");
+    cs.set_fg(&Color::Blue);
+    cs.push_str("e");
+    cs.push_raw_sgr(&[39]);
+    cs.push_str("f");
+
+    let markup = cs.to_markup();
+
+    assert_eq!(
+        markup,
+        "[red]a[bold]b[/bold]c[bold]d[/bold][/red][bold][blue]e[/blue]f[/bold]"
+    );
+    assert_eq!(
+        ColoredString::from_markup(&markup).unwrap().char_colors(),
+        cs.char_colors()
+    );
+}
+
+#[test]
+fn from_markup_nests_tags() {
+    let cs = ColoredString::from_markup("[red][bold]hi[/][/] ok").unwrap();
+
+    assert_eq!(cs.raw(), "hi ok");
+    assert_eq!(cs.colored(), "\x1b[31;1mhi\x1b[0m ok");
+}
+
+#[test]
+fn from_markup_restores_outer_tags() {
+    let cs = ColoredString::from_markup("[on_blue]a[underline]b[/underline]c\\[\\][/]").unwrap();
+
+    assert_eq!(cs.raw(), "abc[]");
+    assert_eq!(cs.colored(), "\x1b[44ma\x1b[4mb\x1b[0;44mc[]\x1b[0m");
+}
+
+#[test]
+fn markup_round_trips() {
+    let mut cs = ColoredString::new();
+
+    cs.set_fg(&Color::Red);
+    cs.push_str("error");
+    cs.reset();
+    cs.push_str(" ok ");
+    cs.set_bg(&Color::BrightBlue);
+    cs.push_str("a");
+    cs.push_raw_sgr(&[1, 38, 5, 40]);
+    cs.push_str("[b]");
+
+    assert_eq!(ColoredString::from_markup(&cs.to_markup()).unwrap(), cs);
+
+    // Disabling an attribute gives the same styling, with other codes.
+    let mut cs = ColoredString::new();
+
+    cs.set_fg(&Color::Red);
+    cs.set_bold(true);
+    cs.push_str("a");
+    cs.set_bold(false);
+    cs.push_str("b");
+
+    let markup = cs.to_markup();
+    let parsed = ColoredString::from_markup(&markup).unwrap();
+
+    assert_eq!(markup, "[red][bold]a[/bold]b[/red]");
+    assert_eq!(parsed.char_colors(), cs.char_colors());
+    assert_eq!(parsed.to_markup(), markup);
+    assert_eq!(parsed.colored(), "\x1b[31;1ma\x1b[0;31mb\x1b[0m");
+}
+
+#[test]
+fn from_markup_reports_malformed_tags() {
+    assert_eq!(
+        ColoredString::from_markup("[red]a"),
+        Err(MarkupError::UnclosedTag {
+            index: 0,
+            name: "red".to_string()
+        })
+    );
+    assert_eq!(
+        ColoredString::from_markup("[red]a[/bold]"),
+        Err(MarkupError::MismatchedTag {
+            index: 6,
+            expected: "red".to_string(),
+            found: "bold".to_string()
+        })
+    );
+    assert_eq!(
+        ColoredString::from_markup("a[/]"),
+        Err(MarkupError::UnexpectedClose { index: 1 })
+    );
+    assert_eq!(
+        ColoredString::from_markup("[purple]a[/]"),
+        Err(MarkupError::UnknownTag {
+            index: 0,
+            name: "purple".to_string()
+        })
+    );
+    assert_eq!(
+        ColoredString::from_markup("a[red"),
+        Err(MarkupError::UnterminatedTag { index: 1 })
+    );
+}
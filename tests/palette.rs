@@ -0,0 +1,60 @@
+use colost::Color;
+use colost::Palette;
+
+#[test]
+fn resolve_named_colors() {
+    let mut palette = Palette::new()
+        .with("error", Color::Red)
+        .with("warn", Color::Yellow);
+
+    assert_eq!(palette.get("error"), Some(Color::Red));
+    assert_eq!(palette.get("warn"), Some(Color::Yellow));
+    assert_eq!(palette.get("info"), None);
+
+    palette.insert("error", Color::BrightRed);
+
+    assert_eq!(palette.get("error"), Some(Color::BrightRed));
+}
+
+#[test]
+fn dark_and_light_themes() {
+    let dark = colost::theme_dark();
+    let light = colost::theme_light();
+
+    assert_eq!(dark.get("error"), Some(Color::BrightRed));
+    assert_eq!(light.get("error"), Some(Color::Red));
+
+    for name in [
+        "error",
+        "warn",
+        "info",
+        "success",
+        "debug",
+        "trace",
+        "muted",
+        "highlight",
+    ] {
+        assert!(dark.get(name).is_some());
+        assert!(light.get(name).is_some());
+    }
+}
+
+#[test]
+fn log_level_colors() {
+    use colost::LogLevel;
+
+    assert_eq!(colost::level_color(LogLevel::Error), Color::Red);
+    assert_eq!(
+        colost::style_level(LogLevel::Error, "boom").colored(),
+        "\x1b[31mboom\x1b[0m"
+    );
+
+    let palette = Palette::new().with("error", Color::BrightRed);
+
+    assert_eq!(palette.level_color(LogLevel::Error), Color::BrightRed);
+    assert_eq!(palette.level_color(LogLevel::Warn), Color::Yellow);
+    assert_eq!(
+        palette.style_level(LogLevel::Error, "boom").colored(),
+        "\x1b[91mboom\x1b[0m"
+    );
+}
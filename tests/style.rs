@@ -0,0 +1,125 @@
+use colost::Color;
+use colost::ColoredString;
+use colost::Style;
+
+#[test]
+fn style_codes_list_attributes_then_colors() {
+    let style = Style::new()
+        .bg(Color::Blue)
+        .underline()
+        .fg(Color::BrightRed)
+        .bold();
+
+    assert_eq!(style.codes(), vec![1, 4, 91, 44]);
+    assert!(Style::default().codes().is_empty());
+}
+
+#[test]
+fn from_styled_spans_styles_each_span() {
+    let cs = ColoredString::from_styled_spans(&[
+        ("error", Style::new().fg(Color::Red).bold()),
+        (": ", Style::new()),
+        ("disk", Style::new().italic()),
+    ]);
+
+    assert_eq!(cs.raw(), "error: disk");
+    assert_eq!(cs.colored(), "\x1b[1;31merror\x1b[0m: \x1b[3mdisk\x1b[0m");
+}
+
+#[test]
+fn restore_style_sets_saved_style_again() {
+    let mut cs = ColoredString::new();
+
+    cs.set_style(&Style::new().fg(Color::Red).underline());
+    cs.push_str("a");
+
+    let saved = cs.save_style();
+
+    cs.set_style(&Style::new().bg(Color::Blue).bold());
+    cs.push_str("b");
+    cs.restore_style(&saved);
+    cs.push_str("c");
+    cs.restore_style(&saved);
+    cs.push_str("d");
+
+    assert_eq!(cs.colored(), "\x1b[4;31ma\x1b[1;44mb\x1b[0;4;31mcd\x1b[0m");
+    assert_eq!(cs.save_style(), saved);
+}
+
+#[test]
+fn pop_style_restores_enclosing_style() {
+    let mut cs = ColoredString::new();
+
+    cs.push_str("a");
+    cs.push_style(&Style::new().fg(Color::Red).bold());
+    cs.push_str("b");
+    cs.push_style(&Style::new().underline());
+    cs.push_str("c");
+    cs.pop_style();
+    cs.push_str("d");
+    cs.pop_style();
+    cs.push_str("e");
+    cs.pop_style();
+    cs.push_str("f");
+
+    assert_eq!(cs.colored(), "a\x1b[1;31mb\x1b[4mc\x1b[0;1;31md\x1b[0mef");
+}
+
+#[test]
+fn transition_codes_only_change_differences() {
+    let from = Style::new()
+        .fg(Color::Red)
+        .bg(Color::Blue)
+        .bold()
+        .underline();
+
+    assert_eq!(
+        colost::transition_codes(&from, &from.fg(Color::Green)),
+        vec![32]
+    );
+    assert_eq!(
+        colost::transition_codes(&from, &Style::new().fg(Color::Red).faint().italic()),
+        vec![22, 2, 3, 24, 49]
+    );
+    assert_eq!(
+        colost::transition_codes(&Style::new().bold().faint(), &Style::new().faint()),
+        vec![22, 2]
+    );
+    assert!(colost::transition_codes(&from, &from).is_empty());
+    assert_eq!(colost::transition_codes(&Style::new(), &from), from.codes());
+}
+
+#[test]
+fn char_colors_fold_markers() {
+    use colost::{ActiveColor, ActiveStyle};
+
+    let mut cs = ColoredString::new();
+
+    cs.set_fg(&Color::Red);
+    cs.push_str("ab");
+    cs.set_fg_256(40);
+    cs.set_bold(true);
+    cs.push_str("c");
+    cs.reset();
+    cs.push_str("d");
+
+    let red = ActiveStyle {
+        fg: Some(ActiveColor::Basic(Color::Red)),
+        ..ActiveStyle::default()
+    };
+    let green = ActiveStyle {
+        fg: Some(ActiveColor::Palette(40)),
+        bold: true,
+        ..ActiveStyle::default()
+    };
+
+    assert_eq!(
+        cs.char_colors(),
+        vec![
+            ('a', red),
+            ('b', red),
+            ('c', green),
+            ('d', ActiveStyle::default())
+        ]
+    );
+}
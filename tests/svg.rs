@@ -0,0 +1,41 @@
+#![cfg(feature = "svg")]
+
+use colost::Color;
+use colost::ColoredString;
+
+#[test]
+fn red_word_is_a_filled_tspan() {
+    let mut cs = ColoredString::new();
+
+    cs.push_str("a ");
+    cs.set_fg(&Color::Red);
+    cs.push_str("red");
+    cs.reset();
+    cs.push_str(" <b>");
+
+    let svg = cs.to_svg();
+
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"81\""));
+    assert!(svg.contains(
+        "<text x=\"0\" y=\"14\" xml:space=\"preserve\">a <tspan fill=\"#cd0000\">red</tspan> &lt;b&gt;</text>"
+    ));
+    assert!(svg.ends_with("</svg>"));
+}
+
+#[test]
+fn background_and_bold() {
+    let mut cs = ColoredString::new();
+
+    cs.push_str("x\n ");
+    cs.set_bg(&Color::Blue);
+    cs.set_bold(true);
+    cs.push_str("ok");
+
+    let svg = cs.to_svg();
+
+    assert!(svg.contains("height=\"36\""));
+    assert!(svg.contains("<rect x=\"9\" y=\"18\" width=\"18\" height=\"18\" fill=\"#0000ee\"/>"));
+    assert!(svg.contains(
+        "<text x=\"0\" y=\"32\" xml:space=\"preserve\"> <tspan font-weight=\"bold\">ok</tspan></text>"
+    ));
+}
@@ -0,0 +1,56 @@
+use colost::Color;
+use colost::ColoredString;
+use colost::Table;
+
+fn fragment(text: &str, color: &Color) -> ColoredString {
+    let mut cs = ColoredString::new();
+
+    cs.set_fg(color);
+    cs.push_str(text);
+    cs
+}
+
+#[test]
+fn columns_align_despite_colors() {
+    let table = Table::new()
+        .with_row(vec![fragment("name", &Color::Red), "size".into()])
+        .with_row(vec!["a".into(), fragment("12", &Color::Green)]);
+    let rendered = table.render();
+
+    assert_eq!(rendered.raw(), "name  size\na     12");
+    assert_eq!(
+        rendered.colored(),
+        "\x1b[31mname\x1b[0m  size\na     \x1b[32m12\x1b[0m"
+    );
+}
+
+#[test]
+fn border_frames_cells() {
+    let table = Table::new()
+        .with_border(true)
+        .with_row(vec![fragment("ab", &Color::Blue), "c".into()])
+        .with_row(vec!["d".into()]);
+
+    assert_eq!(
+        table.render().raw(),
+        "┌────┬───┐\n│ ab │ c │\n│ d  │   │\n└────┴───┘"
+    );
+}
+
+#[test]
+fn multiline_cells_make_taller_rows() {
+    let table = Table::new()
+        .with_border(true)
+        .with_row(vec![fragment("ab\nc", &Color::Blue), "d".into()])
+        .with_row(vec!["e".into(), "f".into()]);
+    let rendered = table.render();
+
+    assert_eq!(
+        rendered.raw(),
+        "┌────┬───┐\n│ ab │ d │\n│ c  │   │\n│ e  │ f │\n└────┴───┘"
+    );
+    assert_eq!(
+        rendered.colored(),
+        "┌────┬───┐\n│ \x1b[34mab\x1b[0m │ d │\n│ \x1b[34mc\x1b[0m  │   │\n│ e  │ f │\n└────┴───┘"
+    );
+}
@@ -0,0 +1,50 @@
+use colost::{Color, ColorLevel, ColoredString};
+use std::sync::Mutex;
+
+/// The tests change the global palette, they must not run concurrently.
+static LOCK: Mutex<()> = Mutex::new(());
+
+/// Default xterm palette with blue replaced by the one of Solarized.
+fn solarized_blue() -> [(u8, u8, u8); 16] {
+    let mut palette = Color::ALL.map(|color| color.rgb());
+
+    palette[Color::Blue as usize] = (38, 139, 210);
+    palette
+}
+
+#[test]
+fn palette_drives_downgrades() {
+    let _lock = LOCK.lock().unwrap();
+    let cs = ColoredString::parse_ansi("\x1b[38;2;38;139;210mblue\x1b[0m");
+
+    assert_eq!(cs.render_for(ColorLevel::Ansi16), "\x1b[36mblue\x1b[0m");
+
+    colost::set_rgb_palette(solarized_blue());
+
+    assert_eq!(cs.render_for(ColorLevel::Ansi16), "\x1b[34mblue\x1b[0m");
+    assert_eq!(Color::Blue.rgb(), (0, 0, 238));
+
+    colost::clear_rgb_palette();
+
+    assert_eq!(cs.render_for(ColorLevel::Ansi16), "\x1b[36mblue\x1b[0m");
+}
+
+#[test]
+#[cfg(feature = "svg")]
+fn palette_drives_svg_fill() {
+    let _lock = LOCK.lock().unwrap();
+    let mut cs = ColoredString::new();
+
+    cs.set_fg(&Color::Blue);
+    cs.push_str("blue");
+
+    assert!(cs.to_svg().contains("<tspan fill=\"#0000ee\">blue</tspan>"));
+
+    colost::set_rgb_palette(solarized_blue());
+
+    let svg = cs.to_svg();
+
+    colost::clear_rgb_palette();
+
+    assert!(svg.contains("<tspan fill=\"#268bd2\">blue</tspan>"));
+}
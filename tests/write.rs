@@ -0,0 +1,120 @@
+#![cfg(feature = "std")]
+
+use colost::Color;
+use colost::ColoredString;
+
+#[test]
+fn write_to_file_strips_colors() {
+    let path = std::env::temp_dir().join(format!("colost-{}.txt", std::process::id()));
+    let mut cs = ColoredString::new();
+
+    cs.set_fg(&Color::Red);
+    cs.push_str("hello\nworld");
+    cs.write_to_file(&path).unwrap();
+
+    let content = std::fs::read_to_string(&path).unwrap();
+
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(content, "hello\nworld");
+}
+
+#[test]
+fn colorize_stream_colors_each_line() {
+    let input = "ok\nERROR: boom\r\nlast";
+    let mut output = Vec::new();
+
+    colost::colorize_stream(input.as_bytes(), &mut output, |line| {
+        let mut cs = ColoredString::new();
+
+        if line.starts_with("ERROR") {
+            cs.set_fg(&Color::Red);
+        }
+
+        cs.push_str(line);
+        cs
+    })
+    .unwrap();
+
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        "ok\n\x1b[31mERROR: boom\x1b[0m\r\nlast"
+    );
+}
+
+#[test]
+fn write_to_writes_colored_output() {
+    let mut cs = ColoredString::new();
+    let mut output = Vec::new();
+
+    cs.set_fg(&Color::Green);
+    cs.push_str("hi");
+    cs.write_to(&mut output).unwrap();
+
+    assert_eq!(output, cs.colored().as_bytes());
+}
+
+/// Writer accepting at most `chunk` bytes per call and failing once `limit` bytes are written.
+struct Flaky {
+    written: Vec<u8>,
+    chunk: usize,
+    limit: usize,
+}
+
+impl std::io::Write for Flaky {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if self.written.len() >= self.limit {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::BrokenPipe,
+                "closed",
+            ));
+        }
+
+        let len = buf
+            .len()
+            .min(self.chunk)
+            .min(self.limit - self.written.len());
+
+        self.written.extend_from_slice(&buf[..len]);
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn write_to_retries_short_writes() {
+    let mut cs = ColoredString::new();
+    let mut writer = Flaky {
+        written: Vec::new(),
+        chunk: 2,
+        limit: usize::MAX,
+    };
+
+    cs.set_fg(&Color::Red);
+    cs.push_str("hello");
+    cs.write_to(&mut writer).unwrap();
+
+    assert_eq!(writer.written, cs.colored().as_bytes());
+}
+
+#[test]
+fn write_to_surfaces_errors() {
+    let mut cs = ColoredString::new();
+    let mut writer = Flaky {
+        written: Vec::new(),
+        chunk: 3,
+        limit: 7,
+    };
+
+    cs.set_fg(&Color::Red);
+    cs.push_str("hello");
+
+    let error = cs.write_to(&mut writer).unwrap_err();
+
+    assert_eq!(error.kind(), std::io::ErrorKind::BrokenPipe);
+    assert_eq!(writer.written, b"\x1b[31mhe");
+    assert_eq!(cs.colored(), "\x1b[31mhello\x1b[0m");
+}